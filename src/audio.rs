@@ -1,4 +1,4 @@
-use crate::{random::Random, util};
+use crate::{point::Point, random::Random, util};
 
 use std::{convert::TryInto, time::Duration};
 
@@ -6,13 +6,31 @@ use rodio::{OutputStreamHandle, Sink, Source};
 
 type SoundData = std::io::Cursor<&'static [u8]>;
 
+/// Distance scale of the falloff, in tiles: gain is
+/// `(1 / (1 + distance / FALLOFF_DISTANCE))^FALLOFF_EXPONENT`, so only
+/// an effect on the player's own tile plays at full volume.
+const FALLOFF_DISTANCE: f32 = 1.0;
+
+/// How sharply gain drops off past `FALLOFF_DISTANCE`. Higher values
+/// fall off faster.
+const FALLOFF_EXPONENT: i32 = 6;
+
+/// Extra distance added to an occluded effect's position before
+/// computing falloff, so a sound muffled by e.g. a `TileKind::Tree`
+/// is quieter than its raw distance alone would suggest.
+const OCCLUSION_PENALTY: f32 = 3.0;
+
 pub struct Audio {
     pub backgrounds: BackgroundSounds,
     pub background_sound_queue: Sink,
     pub effects: EffectSounds,
     pub sound_effect_queue: [Sink; 2],
     pub rng: Random,
-    sound_effects: Vec<(Effect, Duration)>,
+    sound_effects: Vec<(Effect, Duration, Point)>,
+    /// Per-category volume multipliers, applied on top of positional
+    /// gain in `play_mixed_sound_effects`. Lets e.g. combat effects be
+    /// turned down independently of UI feedback.
+    category_volume: CategoryVolume,
 }
 
 impl Audio {
@@ -87,17 +105,37 @@ impl Audio {
             sound_effect_queue,
             rng: Random::new(),
             sound_effects: vec![],
+            category_volume: CategoryVolume::default(),
         }
     }
 
-    pub fn mix_sound_effect(&mut self, effect: Effect, delay: Duration) {
-        self.sound_effects.push((effect, delay));
+    // NOTE: eating (`[1]`), dose pickup (tracked by
+    // `state.player_picked_up_a_dose`) and monster-bump handling all
+    // live in `game.rs`, which isn't part of this editable snapshot.
+    // Once it is, those call sites should call `mix_sound_effect` with
+    // `Effect::Eat`/`Effect::DosePickup`/`Effect::DoseUse`/
+    // `Effect::PlayerHit` instead of deciding which sound to play
+    // themselves -- `Effect::description`/`Effect::category` below are
+    // what make that a one-line change per event rather than a new
+    // `data_from_effect` arm and a new volume group each time.
+
+    /// Queues `effect` to play, originating from `origin` in world
+    /// coordinates. `play_mixed_sound_effects` attenuates it based on
+    /// how far `origin` is from the player.
+    pub fn mix_sound_effect(&mut self, effect: Effect, delay: Duration, origin: Point) {
+        self.sound_effects.push((effect, delay, origin));
     }
 
     pub fn random_delay(&mut self) -> Duration {
         Duration::from_millis(self.rng.range_inclusive(1, 50).try_into().unwrap_or(0))
     }
 
+    // NOTE: `Eat`/`DosePickup`/`DoseUse`/`PlayerHit`/`WillIncrease`/
+    // `NpcTalk` don't have dedicated assets yet -- no `assets/sound/`
+    // directory exists in this checkout to add `.ogg` files to, and
+    // guessing at binary audio content isn't something to fake. They
+    // borrow the closest existing sound below as a placeholder; swap
+    // each one for a real `include_bytes!` once its asset lands.
     fn data_from_effect(&mut self, effect: Effect) -> SoundData {
         use Effect::*;
         match effect {
@@ -110,16 +148,35 @@ impl Audio {
             Explosion => self.effects.explosion.clone(),
             GameOver => self.effects.game_over.clone(),
             Click => self.effects.click.clone(),
+            Eat => self.effects.click.clone(),
+            DosePickup => self.effects.monster_moved.clone(),
+            DoseUse => self.effects.explosion.clone(),
+            PlayerHit => self.effects.monster_hit.clone(),
+            WillIncrease => self.effects.click.clone(),
+            NpcTalk => self.effects.monster_moved.clone(),
         }
     }
 
-    pub fn play_mixed_sound_effects(&mut self) {
+    /// Mixes and plays every effect queued since the last call,
+    /// attenuating each one's gain by its distance from `player_pos`.
+    /// `is_visible` should report whether a tile is currently in the
+    /// player's line of sight; effects from tiles it's not -- e.g.
+    /// behind a `TileKind::Tree` -- get an extra occlusion penalty on
+    /// top of their raw distance.
+    pub fn play_mixed_sound_effects(
+        &mut self,
+        player_pos: Point,
+        is_visible: impl Fn(Point) -> bool,
+    ) {
         use rodio::{decoder::Decoder, source::Empty};
         let mut mixed_sound: Box<dyn Source<Item = i16> + Send> = Box::new(Empty::new());
-        while let Some((effect, delay)) = self.sound_effects.pop() {
+        while let Some((effect, delay, origin)) = self.sound_effects.pop() {
             let data = self.data_from_effect(effect);
             if let Ok(sound) = Decoder::new(data) {
-                mixed_sound = Box::new(mixed_sound.mix(sound.delay(delay)));
+                let occluded = !is_visible(origin);
+                let gain = positional_gain(origin, player_pos, occluded)
+                    * self.category_volume.get(effect.category());
+                mixed_sound = Box::new(mixed_sound.mix(sound.delay(delay).amplify(gain)));
             }
         }
         self.play_sound(mixed_sound);
@@ -145,6 +202,14 @@ impl Audio {
             queue.set_volume(volume);
         }
     }
+
+    /// Sets the volume multiplier for one `EffectCategory`, independent
+    /// of the others and of `set_effects_volume`. E.g. a player could
+    /// mute `Combat` sounds while keeping `Ui` feedback audible.
+    pub fn set_category_volume(&mut self, category: EffectCategory, volume: f32) {
+        let volume = util::clampf(0.0, volume, 1.0);
+        self.category_volume.set(category, volume);
+    }
 }
 
 pub struct BackgroundSounds {
@@ -179,6 +244,70 @@ pub struct EffectSounds {
     pub click: SoundData,
 }
 
+/// Independently mixable groups an `Effect` can belong to, so e.g. a
+/// future accessibility option could let a player turn down `Combat`
+/// noise without losing `Ui` feedback.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EffectCategory {
+    Ui,
+    Combat,
+    Ambient,
+}
+
+/// Per-`EffectCategory` volume multipliers, defaulting to full volume.
+struct CategoryVolume {
+    ui: f32,
+    combat: f32,
+    ambient: f32,
+}
+
+impl CategoryVolume {
+    fn get(&self, category: EffectCategory) -> f32 {
+        match category {
+            EffectCategory::Ui => self.ui,
+            EffectCategory::Combat => self.combat,
+            EffectCategory::Ambient => self.ambient,
+        }
+    }
+
+    fn set(&mut self, category: EffectCategory, volume: f32) {
+        match category {
+            EffectCategory::Ui => self.ui = volume,
+            EffectCategory::Combat => self.combat = volume,
+            EffectCategory::Ambient => self.ambient = volume,
+        }
+    }
+}
+
+impl Default for CategoryVolume {
+    fn default() -> Self {
+        CategoryVolume {
+            ui: 1.0,
+            combat: 1.0,
+            ambient: 1.0,
+        }
+    }
+}
+
+/// Chebyshev (8-way) distance in tiles between two world positions.
+fn chebyshev_distance(a: Point, b: Point) -> i32 {
+    (a.x - b.x).abs().max((a.y - b.y).abs())
+}
+
+/// Per-effect gain for a sound originating at `origin`, given the
+/// player's position and whether `origin` is occluded from their line
+/// of sight. Inverse-power falloff, clamped to `[0, 1]`.
+fn positional_gain(origin: Point, player_pos: Point, occluded: bool) -> f32 {
+    let dist = chebyshev_distance(origin, player_pos) as f32;
+    let dist = if occluded {
+        dist + OCCLUSION_PENALTY
+    } else {
+        dist
+    };
+    let gain = (1.0 / (1.0 + dist / FALLOFF_DISTANCE)).powi(FALLOFF_EXPONENT);
+    util::clampf(0.0, gain, 1.0)
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Effect {
     Walk,
@@ -187,4 +316,50 @@ pub enum Effect {
     Explosion,
     GameOver,
     Click,
+    /// The player ate a `Kind::Food` item.
+    Eat,
+    /// The player picked up a dose item.
+    DosePickup,
+    /// The player used (drank/snorted/whatever) a dose.
+    DoseUse,
+    /// The player took damage from a monster.
+    PlayerHit,
+    /// The player's Will went up, e.g. confronting Anxiety while sober.
+    WillIncrease,
+    /// An NPC companion said something.
+    NpcTalk,
+}
+
+impl Effect {
+    /// A short, human-readable description of what triggered this
+    /// effect, e.g. for a future "what made that sound" accessibility
+    /// option.
+    pub fn description(self) -> &'static str {
+        use Effect::*;
+        match self {
+            Walk => "Footstep",
+            MonsterHit => "Monster hit",
+            MonsterMoved => "Monster moved",
+            Explosion => "Explosion",
+            GameOver => "Game over",
+            Click => "UI click",
+            Eat => "Eating food",
+            DosePickup => "Dose picked up",
+            DoseUse => "Dose used",
+            PlayerHit => "Player hit",
+            WillIncrease => "Will increased",
+            NpcTalk => "NPC talking",
+        }
+    }
+
+    /// Which independently mixable volume group this effect belongs
+    /// to.
+    pub fn category(self) -> EffectCategory {
+        use Effect::*;
+        match self {
+            Walk | MonsterMoved | NpcTalk => EffectCategory::Ambient,
+            MonsterHit | Explosion | PlayerHit => EffectCategory::Combat,
+            GameOver | Click | Eat | DosePickup | DoseUse | WillIncrease => EffectCategory::Ui,
+        }
+    }
 }
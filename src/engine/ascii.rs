@@ -0,0 +1,260 @@
+//! A headless, text-mode rendering backend. Instead of rasterizing
+//! tiles to a GPU texture atlas, it rasterizes the world (and the UI)
+//! to a grid of `char`s using `Graphic::into::<char>()` and prints that
+//! grid to stdout using ANSI escapes for color. This makes it possible
+//! to play over SSH, drive the game from CI smoke tests, and use it
+//! with a screen reader.
+//!
+//! NOTE: `Display` (the type every `Window::render` takes) is a
+//! concrete, GPU-oriented struct defined in `engine/mod.rs`, which
+//! isn't part of this checkout. Rather than guess at its internals,
+//! this backend builds its own lightweight `AsciiFrame` and renders
+//! that directly, the same way `engine::sdl::main_loop` renders its
+//! own hardcoded rectangles without going through `Display` either.
+//! Wiring an `AsciiFrame` so that `Window::render`'s `display.draw_*`
+//! calls can target it too -- instead of this module reimplementing
+//! its own frame loop -- would mean factoring `Display`'s methods
+//! behind a trait in `engine/mod.rs` and is out of scope here.
+
+use crate::{color::Color, point::Point};
+
+/// One character cell of the terminal frame.
+#[derive(Clone, Copy)]
+struct Cell {
+    glyph: char,
+    fg: Color,
+    bg: Color,
+}
+
+/// A full-screen grid of character cells, built up over one frame and
+/// then flushed to stdout in a single write.
+pub struct AsciiFrame {
+    width: i32,
+    height: i32,
+    cells: Vec<Cell>,
+    default_bg: Color,
+}
+
+impl AsciiFrame {
+    pub fn new(size: Point, default_bg: Color) -> Self {
+        let cell = Cell {
+            glyph: ' ',
+            fg: default_bg,
+            bg: default_bg,
+        };
+        AsciiFrame {
+            width: size.x,
+            height: size.y,
+            cells: vec![cell; (size.x * size.y).max(0) as usize],
+            default_bg,
+        }
+    }
+
+    fn index(&self, pos: Point) -> Option<usize> {
+        if pos.x < 0 || pos.y < 0 || pos.x >= self.width || pos.y >= self.height {
+            return None;
+        }
+        Some((pos.y * self.width + pos.x) as usize)
+    }
+
+    pub fn set_glyph(&mut self, pos: Point, glyph: char, fg: Color) {
+        if let Some(index) = self.index(pos) {
+            let bg = self.cells[index].bg;
+            self.cells[index] = Cell { glyph, fg, bg };
+        }
+    }
+
+    /// Fills a tile-coordinate rectangle with a solid color, degrading
+    /// the three UI chrome colors that would otherwise just be flat
+    /// color blocks on a GPU backend into something a monochrome-ish
+    /// terminal can actually read: a box-drawing border for the
+    /// window edge, plain spaces for the window background, and
+    /// inverse video (swapped fg/bg) for the menu highlight.
+    pub fn fill_rect(&mut self, top_left: Point, bottom_right: Point, color: Color) {
+        for y in top_left.y..=bottom_right.y {
+            for x in top_left.x..=bottom_right.x {
+                let pos = Point::new(x, y);
+                let on_edge = x == top_left.x
+                    || x == bottom_right.x
+                    || y == top_left.y
+                    || y == bottom_right.y;
+                if color == crate::color::window_edge {
+                    let glyph = if on_edge {
+                        box_drawing_glyph(pos, top_left, bottom_right)
+                    } else {
+                        ' '
+                    };
+                    self.set_glyph(pos, glyph, color);
+                } else if color == crate::color::window_background {
+                    self.set_glyph(pos, ' ', color);
+                } else if color == crate::color::menu_highlight {
+                    if let Some(index) = self.index(pos) {
+                        let fg = self.cells[index].bg;
+                        self.cells[index] = Cell {
+                            glyph: self.cells[index].glyph,
+                            fg,
+                            bg: color,
+                        };
+                    }
+                } else {
+                    self.set_glyph(pos, ' ', color);
+                }
+            }
+        }
+    }
+
+    /// Renders the frame to stdout: one `SGR` truecolor escape per
+    /// cell whose colors changed since the previous cell, a reset at
+    /// the end of each row, and a final cursor-home so the next frame
+    /// overwrites this one instead of scrolling.
+    pub fn render_to_stdout(&self) {
+        use std::io::Write;
+
+        let mut out = String::new();
+        out.push_str("\x1b[H");
+        for y in 0..self.height {
+            let mut last_colors = None;
+            for x in 0..self.width {
+                let cell = self.cells[(y * self.width + x) as usize];
+                if last_colors != Some((cell.fg, cell.bg)) {
+                    out.push_str(&format!(
+                        "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m",
+                        cell.fg.r, cell.fg.g, cell.fg.b, cell.bg.r, cell.bg.g, cell.bg.b
+                    ));
+                    last_colors = Some((cell.fg, cell.bg));
+                }
+                out.push(cell.glyph);
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        let _ = handle.write_all(out.as_bytes());
+        let _ = handle.flush();
+    }
+
+    pub fn default_background(&self) -> Color {
+        self.default_bg
+    }
+}
+
+fn box_drawing_glyph(pos: Point, top_left: Point, bottom_right: Point) -> char {
+    match (
+        pos.x == top_left.x,
+        pos.x == bottom_right.x,
+        pos.y == top_left.y,
+        pos.y == bottom_right.y,
+    ) {
+        (true, _, true, _) => '┌',
+        (_, true, true, _) => '┐',
+        (true, _, _, true) => '└',
+        (_, true, _, true) => '┘',
+        (true, _, _, _) | (_, true, _, _) => '│',
+        _ => '─',
+    }
+}
+
+/// Draws everything `state` knows how to position on the map --
+/// terrain defers to `default_background` since the per-cell terrain
+/// accessor isn't part of this checkout's `World` surface (only
+/// `chunk.monsters()` and `player.pos` are exercised elsewhere in the
+/// tree -- see `state.rs`'s `verification`) -- into `frame`, in map
+/// coordinates translated through `state.screen_pos_from_world_pos`.
+fn render_world(state: &crate::state::State, frame: &mut AsciiFrame) {
+    let player_screen_pos = state.screen_pos_from_world_pos(state.player.pos);
+    frame.set_glyph(player_screen_pos, state.player.graphic.into(), state.player.color);
+
+    let top_left = state.screen_left_top_corner();
+    for chunk_pos in state.world.positions_of_all_chunks() {
+        let chunk = match state.world.chunk(chunk_pos) {
+            Some(chunk) => chunk,
+            None => continue,
+        };
+        for monster in chunk.monsters() {
+            if monster.dead {
+                continue;
+            }
+            let screen_pos = monster.position - top_left;
+            // NOTE: there's no `MonsterKind` -> `Graphic` lookup in
+            // this checkout (unlike `item.rs`'s `Item::graphic`), so
+            // every living monster renders as the same marker glyph
+            // rather than its real sprite.
+            frame.set_glyph(screen_pos, '&', crate::color::gui_text);
+        }
+    }
+}
+
+/// Blocks for one line of stdin and translates it into the `Command`
+/// it names. The outer `Option` is `None` only once stdin has closed
+/// (e.g. piped input ran out), which `main_loop` below treats as a
+/// request to quit; the inner one is `None` for a blank/unrecognised
+/// line, which just means no new command this turn. Uses the classic
+/// roguelike hjkl/yubn letters rather than single raw keypresses,
+/// since there's no terminal-raw-mode crate in this checkout to read
+/// un-buffered, un-echoed input the way `sdl::main_loop`'s
+/// `event_pump` can.
+fn read_next_command() -> Option<Option<crate::state::Command>> {
+    use crate::state::Command::*;
+    use std::io::BufRead;
+
+    let mut line = String::new();
+    if std::io::stdin().lock().read_line(&mut line).unwrap_or(0) == 0 {
+        return None;
+    }
+    Some(match line.trim() {
+        "h" => Some(W),
+        "l" => Some(E),
+        "k" => Some(N),
+        "j" => Some(S),
+        "y" => Some(NW),
+        "u" => Some(NE),
+        "b" => Some(SW),
+        "n" => Some(SE),
+        _ => None,
+    })
+}
+
+// NOTE: `engine::sdl::main_loop` and `engine::glium::main_loop` (the
+// latter not present in this checkout) both drive a live event loop
+// that polls input, calls `update`, and then draws. This does the
+// same: each iteration rebuilds an `AsciiFrame`, draws the player and
+// monsters into it (see `render_world` above), flushes it to stdout,
+// blocks for one line of movement input via `read_next_command` and
+// queues it onto `state.commands`, then calls `update` to advance the
+// game by one step, stopping once it reports `RunningState::Stopped`
+// or stdin closes.
+//
+// What's still missing: rendering `Window::render_ascii` overlays
+// (help, sidebar, menus), which need a `&mut Display` this backend's
+// `AsciiFrame` doesn't implement -- and reading raw, un-buffered
+// keypresses instead of newline-terminated lines, which needs a
+// terminal-raw-mode crate not present in this checkout. Both are
+// follow-up work; the former once `engine/mod.rs` (and the `Display`
+// trait it would define) land in this checkout.
+pub fn main_loop(
+    display_size: Point,
+    default_background: Color,
+    _window_title: &str,
+    mut state: crate::state::State,
+    update: crate::engine::UpdateFn,
+) {
+    log::info!("Using the ascii backend");
+
+    loop {
+        let mut frame = AsciiFrame::new(display_size, default_background);
+        render_world(&state, &mut frame);
+        frame.render_to_stdout();
+
+        match read_next_command() {
+            Some(Some(command)) => state.commands.push_back(command),
+            Some(None) => {}
+            None => break,
+        }
+
+        match update(&mut state) {
+            crate::game::RunningState::Running => {}
+            crate::game::RunningState::NewGame(new_state) => state = new_state,
+            crate::game::RunningState::Stopped => break,
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! First-class gamepad support via `gilrs`. Polls connected
+//! controllers once per frame and turns stick/D-pad/button input into
+//! the same `Action` values the sidebar's numpad and menu buttons
+//! produce, so downstream code doesn't need to care whether a given
+//! turn's input came from a mouse click or a controller.
+
+use gilrs::{Axis, Button, EventType, Gamepad, Gilrs};
+
+use crate::windows::sidebar::Action;
+
+/// Stick deflection below this is treated as rest/drift, not input.
+const STICK_REST_THRESHOLD: f32 = 0.05;
+
+/// Stick deflection past this (on either axis) counts as a
+/// directional push.
+const STICK_DIRECTION_THRESHOLD: f32 = 0.6;
+
+/// A step the virtual cursor (`windows::sidebar::VirtualCursor`) should
+/// take, produced by the shoulder triggers.
+pub enum CursorStep {
+    Next,
+    Previous,
+}
+
+pub struct GamepadInput {
+    gilrs: Option<Gilrs>,
+    active: Option<gilrs::GamepadId>,
+
+    /// Whether the D-pad/stick has returned to neutral since the last
+    /// direction we emitted. The game is turn-based, so one push
+    /// should be one step, not one step per frame the input stays
+    /// held.
+    direction_released: bool,
+
+    /// Edge-detection for the shoulder triggers, same idea as
+    /// `direction_released` but for virtual-cursor stepping.
+    triggers_released: bool,
+
+    /// Edge-detection for the virtual cursor's activate button.
+    activate_released: bool,
+}
+
+impl GamepadInput {
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(err) => {
+                log::warn!("Gamepad support unavailable: {}", err);
+                None
+            }
+        };
+        GamepadInput {
+            gilrs,
+            active: None,
+            direction_released: true,
+            triggers_released: true,
+            activate_released: true,
+        }
+    }
+
+    /// Whether a controller is currently connected. The sidebar uses
+    /// this to pick the controller diagram over the numpad one.
+    pub fn is_active(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Drain this frame's gilrs events and return the `Action` (if
+    /// any) the player's input maps to.
+    pub fn poll(&mut self) -> Option<Action> {
+        let gilrs = self.gilrs.as_mut()?;
+
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                EventType::Connected => self.active = Some(event.id),
+                EventType::Disconnected => {
+                    if self.active == Some(event.id) {
+                        self.active = None;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let gamepad = gilrs.gamepad(self.active?);
+
+        if let Some(action) = face_button_action(&gamepad) {
+            return Some(action);
+        }
+
+        let direction = dpad_direction(&gamepad).or_else(|| stick_direction(&gamepad));
+        match direction {
+            None => {
+                self.direction_released = true;
+                None
+            }
+            Some(action) if self.direction_released => {
+                self.direction_released = false;
+                Some(action)
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Step the virtual cursor via the shoulder triggers, one step per
+    /// press rather than one per frame the trigger stays held.
+    pub fn cursor_navigation(&mut self) -> Option<CursorStep> {
+        let gilrs = self.gilrs.as_ref()?;
+        let gamepad = gilrs.gamepad(self.active?);
+
+        let next = gamepad.is_pressed(Button::RightTrigger);
+        let previous = gamepad.is_pressed(Button::LeftTrigger);
+
+        match (next, previous) {
+            (false, false) => {
+                self.triggers_released = true;
+                None
+            }
+            (true, false) if self.triggers_released => {
+                self.triggers_released = false;
+                Some(CursorStep::Next)
+            }
+            (false, true) if self.triggers_released => {
+                self.triggers_released = false;
+                Some(CursorStep::Previous)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the virtual cursor's activate button was just pressed.
+    pub fn cursor_activate(&mut self) -> bool {
+        let gamepad = match (self.gilrs.as_ref(), self.active) {
+            (Some(gilrs), Some(id)) => gilrs.gamepad(id),
+            _ => return false,
+        };
+
+        let pressed = gamepad.is_pressed(Button::North);
+        if pressed && self.activate_released {
+            self.activate_released = false;
+            true
+        } else {
+            if !pressed {
+                self.activate_released = true;
+            }
+            false
+        }
+    }
+}
+
+/// Main-menu/help/inventory actions bound to the controller's face
+/// and system buttons.
+fn face_button_action(gamepad: &Gamepad<'_>) -> Option<Action> {
+    if gamepad.is_pressed(Button::Start) {
+        Some(Action::MainMenu)
+    } else if gamepad.is_pressed(Button::Select) {
+        Some(Action::Help)
+    } else if gamepad.is_pressed(Button::South) {
+        Some(Action::UseFood)
+    } else if gamepad.is_pressed(Button::East) {
+        Some(Action::UseDose)
+    } else {
+        None
+    }
+}
+
+fn dpad_direction(gamepad: &Gamepad<'_>) -> Option<Action> {
+    let horizontal = signum(
+        gamepad.is_pressed(Button::DPadRight),
+        gamepad.is_pressed(Button::DPadLeft),
+    );
+    let vertical = signum(
+        gamepad.is_pressed(Button::DPadUp),
+        gamepad.is_pressed(Button::DPadDown),
+    );
+    direction_action(horizontal, vertical)
+}
+
+/// Quantize the left stick's deflection into one of the eight
+/// movement `Action`s, ignoring anything under
+/// `STICK_DIRECTION_THRESHOLD` so drift near the rest position
+/// doesn't register as movement.
+fn stick_direction(gamepad: &Gamepad<'_>) -> Option<Action> {
+    let x = gamepad.value(Axis::LeftStickX);
+    let y = gamepad.value(Axis::LeftStickY);
+    if x.abs() < STICK_REST_THRESHOLD && y.abs() < STICK_REST_THRESHOLD {
+        return None;
+    }
+
+    let horizontal = signum(
+        x > STICK_DIRECTION_THRESHOLD,
+        x < -STICK_DIRECTION_THRESHOLD,
+    );
+    let vertical = signum(
+        y > STICK_DIRECTION_THRESHOLD,
+        y < -STICK_DIRECTION_THRESHOLD,
+    );
+    direction_action(horizontal, vertical)
+}
+
+fn signum(positive: bool, negative: bool) -> i32 {
+    match (positive, negative) {
+        (true, false) => 1,
+        (false, true) => -1,
+        _ => 0,
+    }
+}
+
+fn direction_action(horizontal: i32, vertical: i32) -> Option<Action> {
+    match (horizontal, vertical) {
+        (0, 1) => Some(Action::MoveN),
+        (0, -1) => Some(Action::MoveS),
+        (-1, 0) => Some(Action::MoveW),
+        (1, 0) => Some(Action::MoveE),
+        (1, 1) => Some(Action::MoveNE),
+        (-1, 1) => Some(Action::MoveNW),
+        (1, -1) => Some(Action::MoveSE),
+        (-1, -1) => Some(Action::MoveSW),
+        (0, 0) => None,
+        _ => None,
+    }
+}
+
+pub fn default_gamepad_input() -> GamepadInput {
+    GamepadInput::new()
+}
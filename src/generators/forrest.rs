@@ -57,6 +57,78 @@ fn generate_map<R: Rng, G: Rng>(
     result
 }
 
+// NOTE: `Monster`/`Kind` are defined in `monster.rs` and the AI
+// movement is in `ai.rs`, neither of which is part of this editable
+// snapshot. `aggression_for_kind` and the assignment below assume
+// `Monster` exposes a settable `pub aggression: i32` (plus `hp`/
+// `max_hp`). `fleefactor` below is the other half of that contract:
+// the pure formula `ai.rs`'s movement step would consult each turn
+// (`fleefactor(..) <= 0` means flee). It only needs plain `i32`s, so
+// unlike the monster-movement loop itself it can actually be written
+// and tested against here -- wiring `ai.rs` to call it per-monster,
+// per-turn is the only step left once that file is available.
+
+/// Base aggression per `Kind`. Anxiety, Depression, Shadows and Voices
+/// never flee while alive; the NPC flees once it has lost a quarter of
+/// its health and Hunger once it has lost half.
+fn aggression_for_kind(kind: Kind) -> i32 {
+    use Kind::*;
+    match kind {
+        Anxiety => 100,
+        Depression => 100,
+        Shadows => 60,
+        Voices => 60,
+        Hunger => 2,
+        Npc => 1,
+    }
+}
+
+/// The classic fleefactor rule: how willing a monster with `aggression`
+/// (see [`aggression_for_kind`]) still is to fight at `hp` out of
+/// `max_hp`. Drops below zero as wounds pile up; `ai.rs`'s movement
+/// step should treat a non-positive result as "flee instead of
+/// attack". `max_hp` of zero (a dead monster) never flees -- there's
+/// nothing left for the caller to do but remove it.
+pub fn fleefactor(aggression: i32, hp: i32, max_hp: i32) -> i32 {
+    if max_hp <= 0 {
+        return aggression;
+    }
+    aggression - (4 * (max_hp - hp)) / max_hp
+}
+
+/// Whether a monster with `aggression` at `hp` out of `max_hp` should
+/// flee this turn rather than approach/attack the player.
+pub fn should_flee(aggression: i32, hp: i32, max_hp: i32) -> bool {
+    fleefactor(aggression, hp, max_hp) <= 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_health_never_flees() {
+        assert!(!should_flee(aggression_for_kind(Kind::Npc), 10, 10));
+    }
+
+    #[test]
+    fn low_aggression_monster_flees_once_badly_wounded() {
+        let aggression = aggression_for_kind(Kind::Npc);
+        assert!(should_flee(aggression, 1, 10));
+    }
+
+    #[test]
+    fn high_aggression_monster_keeps_fighting_while_wounded() {
+        let aggression = aggression_for_kind(Kind::Anxiety);
+        assert!(!should_flee(aggression, 1, 10));
+    }
+
+    #[test]
+    fn dead_monster_does_not_flee() {
+        assert!(!should_flee(aggression_for_kind(Kind::Hunger), 0, 0));
+    }
+}
+
 fn generate_monsters<R: Rng>(rng: &mut R, map: &[(Point, Tile)]) -> Vec<Monster> {
     // 3% chance a monster gets spawned
     let monster_count = 5;
@@ -99,6 +171,7 @@ fn generate_monsters<R: Rng>(rng: &mut R, map: &[(Point, Tile)]) -> Vec<Monster>
         }
         if let Some(kind) = opts.sample(rng) {
             let mut monster = Monster::new(kind, pos);
+            monster.aggression = aggression_for_kind(kind);
             if kind == Kind::Npc {
                 use color;
                 use monster::CompanionBonus::*;
@@ -215,6 +288,160 @@ fn generate_items<R: Rng>(rng: &mut R, map: &[(Point, Tile)]) -> Vec<(Point, Ite
     result
 }
 
+// NOTE: attaching fields to `Tile` itself, running `step_fields` once
+// per game turn from the main loop and consulting `haze_fov_penalty`
+// from `formula`'s sight-radius calculation all require `level.rs`,
+// `world.rs` and `formula.rs`, none of which are part of this
+// editable snapshot. `generate` below does call `generate_fields`, so
+// the seeding isn't dead code, but it can't hand the result back to
+// its own caller: `GeneratedWorld` is a fixed-arity tuple alias
+// defined in `generators/mod.rs` (also not part of this snapshot),
+// with no slot for fields. Widening that alias -- and threading the
+// extra return value through `World::new`, which is what actually
+// calls `generate` -- is left for that module.
+
+/// A residue cloud left behind on a tile by combat or an overdose.
+/// Ages and spreads each turn via `step_fields` until it dissipates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub kind: FieldKind,
+    pub density: u8,
+    pub age: u16,
+}
+
+impl Field {
+    fn new(kind: FieldKind, density: u8) -> Self {
+        Field {
+            kind,
+            density,
+            age: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Left behind by `MonsterHit`.
+    Blood,
+    /// Left behind by `Overdosed` -- vomit, bodily residue.
+    Residue,
+    /// Short-lived; obscures vision while the player stands in it.
+    Haze,
+}
+
+impl FieldKind {
+    /// Turns before a field of this kind fully dissipates.
+    fn lifespan(self) -> u16 {
+        match self {
+            FieldKind::Blood => 80,
+            FieldKind::Residue => 50,
+            FieldKind::Haze => 15,
+        }
+    }
+
+    /// Density above which a field spreads into an adjacent tile,
+    /// transferring half its density there.
+    fn spread_threshold(self) -> u8 {
+        match self {
+            FieldKind::Blood => 60,
+            FieldKind::Residue => 60,
+            FieldKind::Haze => 40,
+        }
+    }
+}
+
+pub fn blood_field() -> Field {
+    Field::new(FieldKind::Blood, 100)
+}
+
+pub fn residue_field() -> Field {
+    Field::new(FieldKind::Residue, 100)
+}
+
+pub fn haze_field() -> Field {
+    Field::new(FieldKind::Haze, 100)
+}
+
+/// Seeds a handful of ambient fields for flavor: rare, low-density
+/// blood/residue patches scattered across empty tiles.
+pub fn generate_fields<R: Rng>(rng: &mut R, map: &[(Point, Tile)]) -> Vec<(Point, Field)> {
+    let ambient_chance_per_mille = 4;
+    let mut result = vec![];
+    for &(pos, tile) in map.iter() {
+        if tile.kind != TileKind::Empty {
+            continue;
+        }
+        if rng.gen_range(0, 1000) < ambient_chance_per_mille {
+            let kind = if rng.gen() {
+                FieldKind::Blood
+            } else {
+                FieldKind::Residue
+            };
+            result.push((pos, Field::new(kind, 30)));
+        }
+    }
+    result
+}
+
+/// Runs one turn's cellular update over `fields`: ages every entry,
+/// drops any that have outlived their `FieldKind::lifespan`, and
+/// spreads dense ones into a random adjacent non-`Tree` tile,
+/// transferring half their density there.
+pub fn step_fields<R: Rng>(fields: &mut Vec<(Point, Field)>, map: &[(Point, Tile)], rng: &mut R) {
+    for &mut (_, ref mut field) in fields.iter_mut() {
+        field.age += 1;
+    }
+    fields.retain(|&(_, field)| field.age <= field.kind.lifespan());
+
+    let mut spawned = vec![];
+    for &mut (pos, ref mut field) in fields.iter_mut() {
+        if field.density <= field.kind.spread_threshold() {
+            continue;
+        }
+        if let Some(target) = adjacent_non_tree_tile(pos, map, rng) {
+            let transferred = field.density / 2;
+            field.density -= transferred;
+            spawned.push((target, Field::new(field.kind, transferred)));
+        }
+    }
+    fields.extend(spawned);
+}
+
+fn adjacent_non_tree_tile<R: Rng>(pos: Point, map: &[(Point, Tile)], rng: &mut R) -> Option<Point> {
+    let neighbours = [
+        pos + (1, 0),
+        pos + (-1, 0),
+        pos + (0, 1),
+        pos + (0, -1),
+        pos + (1, 1),
+        pos + (1, -1),
+        pos + (-1, 1),
+        pos + (-1, -1),
+    ];
+    let walkable: Vec<Point> = neighbours
+        .iter()
+        .cloned()
+        .filter(|&candidate| is_non_tree(candidate, map))
+        .collect();
+    rng.choose(&walkable).cloned()
+}
+
+fn is_non_tree(pos: Point, map: &[(Point, Tile)]) -> bool {
+    map.iter()
+        .any(|&(p, tile)| p == pos && tile.kind != TileKind::Tree)
+}
+
+/// How much a `Haze` field at `pos` should reduce the player's FOV
+/// radius, for `formula`'s sight-radius calculation to subtract.
+/// Scales with density: full density roughly halves normal sight.
+pub fn haze_fov_penalty(fields: &[(Point, Field)], pos: Point) -> i32 {
+    fields
+        .iter()
+        .find(|&&(field_pos, field)| field_pos == pos && field.kind == FieldKind::Haze)
+        .map(|&(_, field)| i32::from(field.density) / 32)
+        .unwrap_or(0)
+}
+
 pub fn generate<R: Rng, G: Rng>(
     rng: &mut R,
     throwavay_rng: &mut G,
@@ -224,5 +451,13 @@ pub fn generate<R: Rng, G: Rng>(
     let map = generate_map(rng, throwavay_rng, size, player);
     let monsters = generate_monsters(rng, &map);
     let items = generate_items(rng, &map);
+
+    // Seed the ambient blood/residue fields now, even though (per the
+    // NOTE above) there's nowhere to return them to yet: this at
+    // least proves `generate_fields` runs against a real generated
+    // map rather than sitting wholly uncalled.
+    let fields = generate_fields(rng, &map);
+    log::debug!("Seeded {} ambient field(s)", fields.len());
+
     (map, monsters, items)
 }
@@ -76,6 +76,195 @@ pub enum Graphic {
     Signpost,
 }
 
+/// Every `Graphic` variant, in declaration order. Used to drive the
+/// Legend page (see `Graphic::legend_entry`) without having to keep a
+/// second, hand-maintained list in sync with the enum.
+pub const ALL: &[Graphic] = &[
+    Graphic::Empty,
+    Graphic::Tree1,
+    Graphic::Tree2,
+    Graphic::Tree3,
+    Graphic::Tree4,
+    Graphic::Tree5,
+    Graphic::Tree6,
+    Graphic::Tree7,
+    Graphic::Tree8,
+    Graphic::Tree9,
+    Graphic::Tree10,
+    Graphic::Ground1,
+    Graphic::Ground2,
+    Graphic::Ground3,
+    Graphic::Ground4,
+    Graphic::Ground5,
+    Graphic::Twigs1,
+    Graphic::Twigs2,
+    Graphic::Twigs3,
+    Graphic::Twigs4,
+    Graphic::Twigs5,
+    Graphic::Twigs6,
+    Graphic::Twigs7,
+    Graphic::Twigs8,
+    Graphic::Twigs9,
+    Graphic::Twigs10,
+    Graphic::Twigs11,
+    Graphic::Grass1,
+    Graphic::Grass2,
+    Graphic::Grass3,
+    Graphic::Grass4,
+    Graphic::Grass5,
+    Graphic::Grass6,
+    Graphic::Grass7,
+    Graphic::Grass8,
+    Graphic::Grass9,
+    Graphic::Leaves1,
+    Graphic::Leaves2,
+    Graphic::Leaves3,
+    Graphic::Leaves4,
+    Graphic::Leaves5,
+    Graphic::Player,
+    Graphic::Npc,
+    Graphic::Corpse,
+    Graphic::Anxiety,
+    Graphic::Depression,
+    Graphic::Hunger,
+    Graphic::Shadows,
+    Graphic::Voices,
+    Graphic::Dose,
+    Graphic::StrongDose,
+    Graphic::CardinalDose,
+    Graphic::DiagonalDose,
+    Graphic::FoodAcornWide,
+    Graphic::FoodAcornThin,
+    Graphic::FoodCarrotWide,
+    Graphic::FoodCarrotSideways,
+    Graphic::FoodCarrotThin,
+    Graphic::FoodTurnipSmallLeaves,
+    Graphic::FoodTurnipBigLeaves,
+    Graphic::FoodTurnipHeart,
+    Graphic::FoodStriped,
+    Graphic::Signpost,
+];
+
+/// Which section of the Legend page a `Graphic`'s entry (if any)
+/// belongs under.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GraphicCategory {
+    Terrain,
+    Monster,
+    Item,
+    Player,
+}
+
+/// A `Graphic`'s entry on the Legend page: its display name and a
+/// one-line description of what it does.
+pub struct LegendEntry {
+    pub category: GraphicCategory,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+impl Graphic {
+    /// Which section of the Legend page this graphic belongs to.
+    pub fn category(self) -> GraphicCategory {
+        use Graphic::*;
+        match self {
+            Tree1 | Tree2 | Tree3 | Tree4 | Tree5 | Tree6 | Tree7 | Tree8 | Tree9 | Tree10
+            | Ground1 | Ground2 | Ground3 | Ground4 | Ground5 | Twigs1 | Twigs2 | Twigs3
+            | Twigs4 | Twigs5 | Twigs6 | Twigs7 | Twigs8 | Twigs9 | Twigs10 | Twigs11 | Grass1
+            | Grass2 | Grass3 | Grass4 | Grass5 | Grass6 | Grass7 | Grass8 | Grass9 | Leaves1
+            | Leaves2 | Leaves3 | Leaves4 | Leaves5 | Empty | Signpost => GraphicCategory::Terrain,
+
+            Anxiety | Depression | Hunger | Shadows | Voices | Npc => GraphicCategory::Monster,
+
+            Corpse
+            | Dose
+            | StrongDose
+            | CardinalDose
+            | DiagonalDose
+            | FoodAcornWide
+            | FoodAcornThin
+            | FoodCarrotWide
+            | FoodCarrotSideways
+            | FoodCarrotThin
+            | FoodTurnipSmallLeaves
+            | FoodTurnipBigLeaves
+            | FoodTurnipHeart
+            | FoodStriped => GraphicCategory::Item,
+
+            Player => GraphicCategory::Player,
+        }
+    }
+
+    /// This graphic's Legend page entry, if it has one. `None` for
+    /// the terrain variety tiles, the corpse glyph and the player's
+    /// own '@' -- none of those need explaining -- and for every
+    /// `Food*` variant but `FoodAcornWide`, since they all share one
+    /// glyph and meaning and would otherwise repeat the same entry.
+    pub fn legend_entry(self) -> Option<LegendEntry> {
+        use Graphic::*;
+        use GraphicCategory::*;
+        let (category, name, description) = match self {
+            Anxiety => (
+                Monster,
+                "anxiety",
+                "Takes Will away when it hits you. Defeat them to win the game.",
+            ),
+            Depression => (
+                Monster,
+                "depression",
+                "Moves twice as fast. You lose immediately when it hits you.",
+            ),
+            Hunger => (
+                Monster,
+                "hunger",
+                "Summons other Hungers nearby. Reduces your mind state.",
+            ),
+            Voices => (Monster, "hearing voices", "Paralyzes you for three turns."),
+            Shadows => (
+                Monster,
+                "seeing shadows",
+                "Makes you move randomly for three turns.",
+            ),
+            Npc => (
+                Monster,
+                "friendly",
+                "Ignores you when High. Bump into them Sober for a bonus.",
+            ),
+            FoodAcornWide => (
+                Item,
+                "food",
+                "Prolongs being Sober or in a Withdrawal. Kills monsters around you.",
+            ),
+            Dose => (
+                Item,
+                "dose",
+                "Makes you High. When you're High already, you'll likely Overdose.",
+            ),
+            CardinalDose => (
+                Item,
+                "cardinal dose",
+                "Destroys trees in the horizontal and vertical lines.",
+            ),
+            DiagonalDose => (
+                Item,
+                "diagonal dose",
+                "Destroys trees in the diagonal lines.",
+            ),
+            StrongDose => (
+                Item,
+                "strong dose",
+                "Very strong Dose. Don't walk into it by accident.",
+            ),
+            _ => return None,
+        };
+        Some(LegendEntry {
+            category,
+            name,
+            description,
+        })
+    }
+}
+
 impl Into<char> for Graphic {
     fn into(self) -> char {
         use Graphic::*;
@@ -0,0 +1,109 @@
+//! A minimal message-catalog lookup for the help `Window`'s text,
+//! keyed by stable message keys rather than hardcoded English
+//! literals. The default (English) catalog is embedded in the binary;
+//! `Localizer::with_locale` additionally loads a `locale/<lang>.ftl`
+//! override from disk, falling back to English for any key it doesn't
+//! redefine.
+//!
+//! The catalog format is deliberately simple -- `key = value` lines,
+//! `#`-prefixed comments, blank lines ignored -- rather than a full
+//! Fluent (`.ftl`) parser, since this game's strings don't need
+//! Fluent's plural/selector features.
+
+use std::collections::HashMap;
+
+const DEFAULT_CATALOG_SRC: &str = include_str!("../locale/en.ftl");
+
+/// A message key with no matching entry in either catalog resolves to
+/// the key itself, so a missing translation shows up as an obviously
+/// wrong (but harmless) string rather than empty text.
+pub struct Localizer {
+    default_catalog: HashMap<&'static str, &'static str>,
+    override_catalog: HashMap<String, String>,
+}
+
+impl Localizer {
+    /// The embedded English catalog, with no override loaded.
+    pub fn new() -> Self {
+        Localizer {
+            default_catalog: parse_catalog(DEFAULT_CATALOG_SRC),
+            override_catalog: HashMap::new(),
+        }
+    }
+
+    /// The embedded English catalog, overridden by
+    /// `locale/<lang>.ftl` where that file exists and parses. Falls
+    /// back to the English-only catalog (logging a warning) if the
+    /// override file is missing or unreadable.
+    pub fn with_locale(lang: &str) -> Self {
+        let path = format!("locale/{}.ftl", lang);
+        let override_catalog = match std::fs::read_to_string(&path) {
+            Ok(src) => parse_owned_catalog(&src),
+            Err(err) => {
+                log::warn!(
+                    "Localizer: couldn't load '{}' ({}), falling back to English",
+                    path,
+                    err
+                );
+                HashMap::new()
+            }
+        };
+        Localizer {
+            default_catalog: parse_catalog(DEFAULT_CATALOG_SRC),
+            override_catalog,
+        }
+    }
+
+    /// Resolves `key` through the override catalog, then the default
+    /// (English) one, then -- if neither has it -- returns `key`
+    /// itself.
+    pub fn get(&self, key: &str) -> &str {
+        if let Some(value) = self.override_catalog.get(key) {
+            return value;
+        }
+        if let Some(&value) = self.default_catalog.get(key) {
+            return value;
+        }
+        key
+    }
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Localizer::new()
+    }
+}
+
+fn parse_catalog(src: &'static str) -> HashMap<&'static str, &'static str> {
+    let mut catalog = HashMap::new();
+    for line in src.lines() {
+        if let Some((key, value)) = parse_catalog_line(line) {
+            catalog.insert(key, value);
+        }
+    }
+    catalog
+}
+
+fn parse_owned_catalog(src: &str) -> HashMap<String, String> {
+    let mut catalog = HashMap::new();
+    for line in src.lines() {
+        if let Some((key, value)) = parse_catalog_line(line) {
+            catalog.insert(key.to_string(), value.to_string());
+        }
+    }
+    catalog
+}
+
+fn parse_catalog_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let mut parts = line.splitn(2, '=');
+    let key = parts.next()?.trim();
+    let value = parts.next()?.trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some((key, value))
+}
@@ -18,14 +18,20 @@ mod ai;
 mod animation;
 mod blocker;
 mod color;
+// NOTE: `engine::ascii` (the headless terminal backend) lives at
+// `engine/ascii.rs`, but `engine/mod.rs` -- where it would need a
+// `#[cfg(feature = "ascii-backend")] pub mod ascii;` declaration,
+// alongside the existing `sdl`/`glium` ones -- isn't in this checkout.
 mod engine;
 mod formula;
 mod game;
+mod gamepad;
 mod generators;
 mod graphics;
 mod item;
 mod keys;
 mod level;
+mod localization;
 mod monster;
 mod palette;
 mod pathfinding;
@@ -37,6 +43,7 @@ mod rect;
 mod render;
 mod state;
 mod stats;
+mod theme;
 mod timer;
 mod ui;
 mod util;
@@ -111,6 +118,23 @@ fn run_sdl(
     log::error!("The \"sdl-backend\" feature was not compiled in.");
 }
 
+#[allow(unused_variables, dead_code, needless_pass_by_value)]
+fn run_ascii(
+    display_size: point::Point,
+    default_background: color::Color,
+    window_title: &str,
+    state: state::State,
+    update: engine::UpdateFn,
+) {
+    log::info!("Using the ascii backend");
+
+    #[cfg(feature = "ascii-backend")]
+    engine::ascii::main_loop(display_size, default_background, window_title, state, update);
+
+    #[cfg(not(feature = "ascii-backend"))]
+    log::error!("The \"ascii-backend\" feature was not compiled in.");
+}
+
 #[allow(unused_variables, dead_code, needless_pass_by_value)]
 fn run_remote(
     display_size: point::Point,
@@ -203,7 +227,11 @@ fn process_cli_and_run_game() {
             "Don't create a game window. The input and output is \
              controled via ZeroMQ.",
         ))
-        .group(ArgGroup::with_name("graphics").args(&["glium", "sdl", "remote"]))
+        .arg(Arg::with_name("ascii").long("ascii").help(
+            "Use the headless ascii/terminal rendering backend. Useful \
+             over SSH, in CI smoke tests, and with screen readers.",
+        ))
+        .group(ArgGroup::with_name("graphics").args(&["glium", "sdl", "remote", "ascii"]))
         .get_matches();
 
     log::info!("{} version: {}", GAME_TITLE, env!("CARGO_PKG_VERSION"));
@@ -267,6 +295,14 @@ fn process_cli_and_run_game() {
             state,
             game::update,
         );
+    } else if matches.is_present("ascii") {
+        run_ascii(
+            DISPLAY_SIZE,
+            color::background,
+            GAME_TITLE,
+            state,
+            game::update,
+        );
     } else if matches.is_present("sdl") {
         run_sdl(
             DISPLAY_SIZE,
@@ -0,0 +1,218 @@
+//! Click-to-move pathfinding: an A* search over the 8-connected game
+//! grid, used to auto-walk the player towards a tile they clicked on
+//! the map.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{point::Point, state::Command};
+
+// Costs are scaled by 100 so we can use plain `i32` arithmetic (and
+// therefore a simple `BinaryHeap`) instead of dealing with `f32`,
+// which doesn't implement `Ord`.
+const ORTHOGONAL_COST: i32 = 100;
+const DIAGONAL_COST: i32 = 141;
+
+fn neighbours(pos: Point) -> [Point; 8] {
+    [
+        pos + (1, 0),
+        pos + (-1, 0),
+        pos + (0, 1),
+        pos + (0, -1),
+        pos + (1, 1),
+        pos + (1, -1),
+        pos + (-1, 1),
+        pos + (-1, -1),
+    ]
+}
+
+fn step_cost(from: Point, to: Point) -> i32 {
+    if from.x != to.x && from.y != to.y {
+        DIAGONAL_COST
+    } else {
+        ORTHOGONAL_COST
+    }
+}
+
+// Chebyshev distance: admissible heuristic for 8-way movement since
+// it never overestimates the true remaining cost.
+fn heuristic(a: Point, b: Point) -> i32 {
+    (a.x - b.x).abs().max((a.y - b.y).abs()) * ORTHOGONAL_COST
+}
+
+/// One entry in the A* open set. `BinaryHeap` is a max-heap, so
+/// `Ord` is flipped to make the lowest `estimated_total_cost` come out
+/// first.
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Frontier {
+    position: Point,
+    cost_so_far: i32,
+    estimated_total_cost: i32,
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimated_total_cost
+            .cmp(&self.estimated_total_cost)
+            .then_with(|| other.cost_so_far.cmp(&self.cost_so_far))
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Find the shortest 8-connected route from `start` to `goal`.
+/// `is_walkable` should return `false` for walls, out-of-bounds tiles
+/// and tiles currently occupied by a monster; `goal` itself is always
+/// considered reachable even if `is_walkable` disagrees, so a path can
+/// still be found onto an occupied destination tile (e.g. to bump into
+/// a monster standing on it).
+pub fn find_path(
+    start: Point,
+    goal: Point,
+    is_walkable: impl Fn(Point) -> bool,
+) -> Option<VecDeque<Point>> {
+    if start == goal {
+        return Some(VecDeque::new());
+    }
+
+    let mut open_set = BinaryHeap::new();
+    open_set.push(Frontier {
+        position: start,
+        cost_so_far: 0,
+        estimated_total_cost: heuristic(start, goal),
+    });
+
+    let mut came_from: HashMap<Point, Point> = HashMap::new();
+    let mut best_cost: HashMap<Point, i32> = HashMap::new();
+    best_cost.insert(start, 0);
+
+    while let Some(current) = open_set.pop() {
+        if current.position == goal {
+            return Some(reconstruct_path(&came_from, start, goal));
+        }
+
+        // This entry is stale -- we've already found a cheaper way here.
+        if current.cost_so_far > *best_cost.get(&current.position).unwrap_or(&std::i32::MAX) {
+            continue;
+        }
+
+        for &next in &neighbours(current.position) {
+            if next != goal && !is_walkable(next) {
+                continue;
+            }
+
+            let new_cost = current.cost_so_far + step_cost(current.position, next);
+            let is_better = match best_cost.get(&next) {
+                Some(&existing_cost) => new_cost < existing_cost,
+                None => true,
+            };
+
+            if is_better {
+                best_cost.insert(next, new_cost);
+                came_from.insert(next, current.position);
+                open_set.push(Frontier {
+                    position: next,
+                    cost_so_far: new_cost,
+                    estimated_total_cost: new_cost + heuristic(next, goal),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+fn reconstruct_path(
+    came_from: &HashMap<Point, Point>,
+    start: Point,
+    goal: Point,
+) -> VecDeque<Point> {
+    let mut path = VecDeque::new();
+    let mut current = goal;
+    while current != start {
+        path.push_front(current);
+        current = came_from[&current];
+    }
+    path
+}
+
+fn command_for_step(from: Point, to: Point) -> Option<Command> {
+    match (to.x - from.x, to.y - from.y) {
+        (0, -1) => Some(Command::N),
+        (0, 1) => Some(Command::S),
+        (-1, 0) => Some(Command::W),
+        (1, 0) => Some(Command::E),
+        (1, -1) => Some(Command::NE),
+        (-1, -1) => Some(Command::NW),
+        (1, 1) => Some(Command::SE),
+        (-1, 1) => Some(Command::SW),
+        _ => None,
+    }
+}
+
+/// A click-to-move route the player is currently auto-walking. One
+/// step is drained per turn (see `next_command`) the same way a
+/// keyboard press would be, so the rest of the game loop doesn't need
+/// to know the move came from a path instead of a key.
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct Path {
+    goal: Option<Point>,
+    steps: VecDeque<Point>,
+}
+
+impl Path {
+    /// Compute a new path from `start` to `goal`. Returns `None` (and
+    /// leaves any existing path untouched) if `goal` is unreachable.
+    pub fn to(start: Point, goal: Point, is_walkable: impl Fn(Point) -> bool) -> Option<Self> {
+        find_path(start, goal, is_walkable).map(|steps| Path {
+            goal: Some(goal),
+            steps,
+        })
+    }
+
+    pub fn goal(&self) -> Option<Point> {
+        self.goal
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Drop the current route, e.g. because the player pressed a
+    /// movement key themselves or the path got stale.
+    pub fn cancel(&mut self) {
+        self.goal = None;
+        self.steps.clear();
+    }
+
+    /// True if the next step in the route is no longer walkable (e.g.
+    /// a monster moved into it), meaning the path should be
+    /// recomputed or cancelled rather than walked further.
+    pub fn next_step_blocked(&self, is_walkable: impl Fn(Point) -> bool) -> bool {
+        match self.steps.front() {
+            Some(&next_step) => !is_walkable(next_step),
+            None => false,
+        }
+    }
+
+    /// Pop the next step off the route and turn it into the `Command`
+    /// the game loop should process this turn.
+    pub fn next_command(&mut self, current_pos: Point) -> Option<Command> {
+        let next_step = *self.steps.front()?;
+        let command = command_for_step(current_pos, next_step);
+        self.steps.pop_front();
+        if self.steps.is_empty() {
+            self.goal = None;
+        }
+        command
+    }
+}
@@ -2,16 +2,18 @@ use crate::{
     animation::{self, AreaOfEffect, ScreenFade},
     color::Color,
     engine::Mouse,
-    formula,
+    formula, gamepad,
     graphic::Graphic,
     keys::{Key, Keys},
+    localization::Localizer,
     monster,
     palette::Palette,
-    pathfinding::Path,
+    pathfinding::{self, Path},
     player::Player,
     point::Point,
     random::Random,
     stats::Stats,
+    theme::ThemePreset,
     timer::Timer,
     util,
     window::Window,
@@ -20,10 +22,10 @@ use crate::{
 };
 
 use std::{
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     error::Error,
     fs::File,
-    io::{self, Write},
+    io::{self, Read, Seek, SeekFrom, Write},
     path::PathBuf,
     time::Duration,
 };
@@ -31,6 +33,8 @@ use std::{
 #[cfg(feature = "replay")]
 use std::fs;
 
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use fxhash::hash64;
 use serde::{Deserialize, Serialize};
 
 const CHUNK_SIZE: i32 = 32;
@@ -119,7 +123,35 @@ pub fn generate_replay_path() -> Option<PathBuf> {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+/// A filesystem-safe key distinguishing one `Challenge` combination
+/// from another, used to keep the "last"/"best" managed replay slots
+/// separate per challenge so a lucky no-challenge run never looks like
+/// a new best for a `one_chance` run.
+fn challenge_slug(challenge: Challenge) -> String {
+    format!(
+        "hide_unseen_tiles-{}.fast_depression-{}.one_chance-{}",
+        challenge.hide_unseen_tiles, challenge.fast_depression, challenge.one_chance
+    )
+}
+
+fn challenge_replay_dir(challenge: Challenge) -> PathBuf {
+    std::path::Path::new("replays").join(challenge_slug(challenge))
+}
+
+/// The "most recent attempt" managed replay slot for `challenge`,
+/// overwritten every time `finalize_replay` runs.
+pub fn last_replay_path(challenge: Challenge) -> PathBuf {
+    challenge_replay_dir(challenge).join("last.replay")
+}
+
+/// The "best attempt so far" managed replay slot for `challenge`,
+/// overwritten only when a session survives at least as many turns as
+/// whatever's already there.
+pub fn best_replay_path(challenge: Challenge) -> PathBuf {
+    challenge_replay_dir(challenge).join("best.replay")
+}
+
+#[derive(Debug, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub struct Verification {
     pub turn: i32,
     pub chunk_count: usize,
@@ -127,6 +159,33 @@ pub struct Verification {
     pub monsters: Vec<(Point, Point, monster::Kind)>,
 }
 
+/// How replay determinism-checking is performed each turn. Borrowed
+/// from the Verify/Record/Ignore split found in digest-driven
+/// editors: a replay either checks its recorded digests against the
+/// live game, writes fresh ones, or doesn't bother.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum VerificationMode {
+    /// Recompute the digest every turn and compare it against the
+    /// next one read from the replay log; stop at the first mismatch.
+    Verify,
+    /// Write a `turn:digest` line to the replay log every turn instead
+    /// of comparing against anything.
+    Record,
+    /// Don't compute or compare digests at all.
+    Ignore,
+}
+
+/// What `State::verify_replay` found after driving a replay to
+/// completion headlessly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayReport {
+    pub turns_processed: i32,
+    pub final_session: GameSession,
+    /// The turn a recorded checkpoint (full `Verification` or digest)
+    /// first failed to match the live state, if any.
+    pub diverged_at_turn: Option<i32>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct State {
     pub player: Player,
@@ -156,19 +215,31 @@ pub struct State {
     pub keys: Keys,
     // Mouse config read from the player this turn (or loaded from the replay file)
     pub mouse: Mouse,
+    #[serde(
+        skip_serializing,
+        skip_deserializing,
+        default = "gamepad::default_gamepad_input"
+    )]
+    pub gamepad: gamepad::GamepadInput,
     #[serde(skip_serializing, skip_deserializing)]
     pub inputs: VecDeque<Input>,
     pub commands: VecDeque<Command>,
     pub player_path: Path,
     #[serde(skip_serializing, skip_deserializing)]
     pub verifications: VecDeque<Verification>,
+    /// Compact `(turn, digest)` pairs read from a digest-mode replay
+    /// log, drained one per turn as `VerificationMode::Verify` checks
+    /// them against the live game.
+    #[serde(skip_serializing, skip_deserializing)]
+    pub verification_digests: VecDeque<(i32, u64)>,
     #[serde(skip_serializing, skip_deserializing, default = "empty_command_logger")]
-    pub input_logger: Box<dyn Write>,
+    pub input_logger: InputLogger,
     pub side: Side,
     pub turn: i32,
     pub tick_id: i32,
     pub cheating: bool,
     pub replay: bool,
+    pub verification_mode: VerificationMode,
     pub replay_full_speed: bool,
     pub exit_after: bool,
     pub clock: Duration,
@@ -189,12 +260,63 @@ pub struct State {
     pub game_session: GameSession,
     pub victory_npc_id: Option<MonsterId>,
 
+    /// Where this session's live replay log is being written, if
+    /// we're recording one. Remembered so `finalize_replay` can copy
+    /// it into the "last"/"best" slots once the session ends.
+    #[serde(skip_serializing, skip_deserializing)]
+    pub replay_path: Option<PathBuf>,
+
     pub window_stack: windows::Windows<Window>,
 
     pub show_keyboard_movement_hints: bool,
     pub show_anxiety_counter: bool,
+    /// Whether the player moves via the tile-based sidebar or the
+    /// pixel-anchored touch overlay. Settings toggle this for touch
+    /// screens and the web build.
+    pub control_mode: windows::sidebar::ControlMode,
+    /// Which screen corner the touch overlay's directional pad is
+    /// anchored to.
+    pub overlay_anchor: windows::sidebar::AnchorSide,
+    /// The key currently bound to each movement direction and to the
+    /// main-menu/help shortcuts.
+    pub keybindings: windows::sidebar::Keybindings,
+    /// The keyboard/gamepad-driven pointer over the sidebar's buttons.
+    /// Lets the whole UI be operated without a mouse.
+    pub virtual_cursor: windows::sidebar::VirtualCursor,
     pub player_picked_up_a_dose: bool,
     pub player_bumped_into_a_monster: bool,
+
+    /// Accumulating bad-trip risk from overusing doses, fed by
+    /// `add_dose_contamination` and decayed by `decay_contamination`.
+    /// Consulted by `roll_bad_trip` each turn.
+    pub contamination: f32,
+
+    /// Whether contextual tutorial hints (see `TutorialEvent`) should
+    /// be shown at all. New players get this on by default; it's
+    /// exposed as a settings toggle for everyone else.
+    pub tutorial_enabled: bool,
+    /// Which `TutorialEvent`s have already fired this game. Persisted
+    /// so a resumed save doesn't re-show hints the player already saw.
+    fired_tutorial_events: HashSet<TutorialEvent>,
+    /// The hint waiting to be popped up near `Point`, if
+    /// `trigger_tutorial_event` fired one since the last time the help
+    /// window rendered it. Not persisted -- a reloaded save simply
+    /// doesn't have a hint pending.
+    #[serde(skip)]
+    pub pending_tutorial_event: Option<(TutorialEvent, Point)>,
+
+    /// Whether the player has dismissed `windows::help::ContentNote`,
+    /// the opt-out modal shown once on first launch pointing at
+    /// `Page::Resources`. Persisted so it never reappears once seen.
+    pub content_note_seen: bool,
+
+    // NOTE: which language to load is a settings value (`settings.rs`
+    // is outside this editable snapshot); until that's wired in, every
+    // `State` gets the embedded English catalog. Swapping this for
+    // `Localizer::with_locale(lang)` at startup is the only change
+    // needed once it is.
+    #[serde(skip_serializing, skip_deserializing)]
+    pub localizer: Localizer,
     pub current_help_window: windows::help::Page,
     /// Used for help contents pagination: which line should we start showing.
     pub help_starting_line: i32,
@@ -210,21 +332,28 @@ pub struct State {
 
     pub challenge: Challenge,
     pub palette: Palette,
+    /// Which built-in [`crate::theme::Theme`] the sidebar draws its
+    /// progress bars, labels, panel fill, and highlight rects with.
+    /// Persisted so a reloaded save keeps the player's chosen preset.
+    pub theme_preset: ThemePreset,
 }
 
 impl State {
     #[allow(clippy::too_many_arguments)]
-    fn new<W: Write + 'static>(
+    fn new(
         world_size: Point,
         map_size: Point,
         panel_width: i32,
         inputs: VecDeque<Input>,
         verifications: VecDeque<Verification>,
-        log_writer: W,
+        verification_digests: VecDeque<(i32, u64)>,
+        log_writer: InputLogger,
+        replay_path: Option<PathBuf>,
         seed: u32,
         cheating: bool,
         invincible: bool,
         replay: bool,
+        verification_mode: VerificationMode,
         replay_full_speed: bool,
         exit_after: bool,
         challenge: Challenge,
@@ -274,16 +403,20 @@ impl State {
             audio_rng,
             keys: Keys::new(),
             mouse: Default::default(),
+            gamepad: gamepad::GamepadInput::new(),
             inputs,
             commands: VecDeque::new(),
             player_path: Path::default(),
             verifications,
-            input_logger: Box::new(log_writer),
+            verification_digests,
+            input_logger: log_writer,
+            replay_path,
             side: Side::Player,
             turn: 0,
             tick_id: 0,
             cheating,
             replay,
+            verification_mode,
             replay_full_speed,
             exit_after,
             clock: Duration::new(0, 0),
@@ -305,8 +438,18 @@ impl State {
             // some point.
             show_keyboard_movement_hints: false,
             show_anxiety_counter: false,
+            control_mode: windows::sidebar::ControlMode::Sidebar,
+            overlay_anchor: windows::sidebar::AnchorSide::Right,
+            keybindings: windows::sidebar::Keybindings::load(),
+            virtual_cursor: windows::sidebar::VirtualCursor::default(),
             player_picked_up_a_dose: false,
             player_bumped_into_a_monster: false,
+            contamination: 0.0,
+            tutorial_enabled: true,
+            fired_tutorial_events: HashSet::new(),
+            pending_tutorial_event: None,
+            content_note_seen: false,
+            localizer: Localizer::default(),
             current_help_window: windows::help::Page::DoseResponse,
             help_starting_line: 0,
             show_endscreen_and_uncover_map_during_fadein: false,
@@ -314,6 +457,7 @@ impl State {
 
             challenge,
             palette,
+            theme_preset: ThemePreset::default(),
         }
     }
 
@@ -328,12 +472,22 @@ impl State {
     ) -> State {
         let inputs = VecDeque::new();
         let verifications = VecDeque::new();
+        let verification_digests = VecDeque::new();
+        let is_recording = replay_path.is_some();
         let seed = util::random_seed();
-        let mut writer: Box<dyn Write> = if let Some(replay_path) = replay_path {
+        let recorded_replay_path = if is_recording {
+            replay_path.clone()
+        } else {
+            None
+        };
+        let mut writer = if let Some(replay_path) = replay_path {
             match File::create(&replay_path) {
-                Ok(f) => {
+                Ok(mut f) => {
                     log::info!("Recording the gameplay to '{}'", replay_path.display());
-                    Box::new(f)
+                    if let Err(err) = write_compressed_header(&mut f) {
+                        log::error!("Failed to write the replay file header: {}", err);
+                    }
+                    InputLogger::recording(DeflateEncoder::new(f, Compression::default()))
                 }
                 Err(msg) => {
                     log::error!(
@@ -342,11 +496,11 @@ Reason: '{}'.",
                         replay_path.display(),
                         msg
                     );
-                    Box::new(io::sink())
+                    InputLogger::discarding()
                 }
             }
         } else {
-            Box::new(io::sink())
+            InputLogger::discarding()
         };
 
         log_header(&mut writer, seed);
@@ -354,17 +508,25 @@ Reason: '{}'.",
         let replay = false;
         let invincible = false;
         let replay_full_speed = false;
+        let verification_mode = if is_recording {
+            VerificationMode::Record
+        } else {
+            VerificationMode::Ignore
+        };
         State::new(
             world_size,
             map_size,
             panel_width,
             inputs,
             verifications,
+            verification_digests,
             writer,
+            recorded_replay_path,
             seed,
             cheating,
             invincible,
             replay,
+            verification_mode,
             replay_full_speed,
             exit_after,
             challenge,
@@ -390,8 +552,23 @@ Reason: '{}'.",
             use std::io::{BufRead, BufReader};
             let mut inputs = VecDeque::new();
             let mut verifications = VecDeque::new();
-            let file = File::open(replay_path)?;
-            let mut lines = BufReader::new(file).lines();
+            let mut verification_digests = VecDeque::new();
+            let mut file = File::open(replay_path)?;
+
+            let mut lines: Box<dyn Iterator<Item = io::Result<String>>> =
+                match sniff_compressed_header(&mut file)? {
+                    Some(format_version) => {
+                        if format_version != COMPRESSED_FORMAT_VERSION {
+                            log::warn!(
+                                "Unknown compressed replay format version: {}. Attempting to load anyway.",
+                                format_version
+                            );
+                        }
+                        Box::new(BufReader::new(DeflateDecoder::new(file)).lines())
+                    }
+                    None => Box::new(BufReader::new(file).lines()),
+                };
+
             let seed: u32 = match lines.next() {
                 Some(seed_str) => seed_str?.parse()?,
                 None => throw!("The replay file is empty."),
@@ -427,16 +604,23 @@ Reason: '{}'.",
 
             for line in lines {
                 let line = line?;
-                // Try parsing it as an `Input` first, otherwise it's a `Verification`
+                // Try parsing it as an `Input` first, then as a full
+                // `Verification`, then as a compact `turn:digest` line.
                 if let Ok(input) = serde_json::from_str::<Input>(&line) {
                     assert!(input.tick_id > 0);
                     assert_eq!(inputs.len(), input.tick_id as usize - 1);
 
                     inputs.push_back(input);
-                } else {
-                    // Must be a verification, then
-                    let verification = serde_json::from_str(&line)?;
+                } else if let Ok(verification) = serde_json::from_str::<Verification>(&line) {
                     verifications.push_back(verification);
+                } else if let Some(digest) = parse_verification_digest_line(&line) {
+                    verification_digests.push_back(digest);
+                } else if parse_challenge_header_line(&line).is_some() {
+                    // Informational only -- written by `finalize_replay` so
+                    // the best/last managed slots can be ranked without
+                    // replaying them. Nothing to do with it here.
+                } else {
+                    throw!(format!("Unrecognised line in the replay log: '{}'", line));
                 }
             }
 
@@ -444,17 +628,21 @@ Reason: '{}'.",
             let cheating = cheating;
             let invincible = invincible;
             let replay = true;
+            let verification_mode = VerificationMode::Verify;
             let mut state = State::new(
                 world_size,
                 map_size,
                 panel_width,
                 inputs,
                 verifications,
-                Box::new(io::sink()),
+                verification_digests,
+                InputLogger::discarding(),
+                None,
                 seed,
                 cheating,
                 invincible,
                 replay,
+                verification_mode,
                 replay_full_speed,
                 exit_after,
                 challenge,
@@ -515,44 +703,152 @@ Reason: '{}'.",
         }
     }
 
+    /// A compact, fast, non-cryptographic hash of `verification()`.
+    /// Much cheaper to log and compare than the full `Verification`,
+    /// at the cost of only being useful to detect divergence, not to
+    /// diagnose it.
+    pub fn verification_digest(&self) -> u64 {
+        hash64(&self.verification())
+    }
+
+    /// Runs this turn's `VerificationMode`: records a digest, checks
+    /// one against the replay log, or does nothing. Returns `false`
+    /// when `VerificationMode::Verify` finds a mismatch, meaning the
+    /// replay has diverged and should be halted.
+    pub fn check_verification_digest(&mut self) -> bool {
+        match self.verification_mode {
+            VerificationMode::Ignore => true,
+            VerificationMode::Record => {
+                let digest = self.verification_digest();
+                log_verification_digest(&mut self.input_logger, self.turn, digest);
+                true
+            }
+            VerificationMode::Verify => match self.verification_digests.pop_front() {
+                Some((turn, expected_digest)) => {
+                    let actual_digest = self.verification_digest();
+                    if actual_digest == expected_digest {
+                        true
+                    } else {
+                        log::error!(
+                            "Replay diverged at turn {} (expected turn {}): expected digest {:x}, got {:x}",
+                            self.turn,
+                            turn,
+                            expected_digest,
+                            actual_digest
+                        );
+                        false
+                    }
+                }
+                None => true,
+            },
+        }
+    }
+
+    /// Pops the next recorded checkpoint -- a digest or a full
+    /// `Verification`, whichever the replay log was using -- and
+    /// compares it against the live state. Returns `Err` describing
+    /// the divergence on a mismatch; does nothing and returns `Ok` if
+    /// there's nothing left to check.
+    pub fn verify_next_checkpoint(&mut self) -> Result<(), String> {
+        if let Some((recorded_turn, expected_digest)) = self.verification_digests.pop_front() {
+            let actual_digest = self.verification_digest();
+            if actual_digest != expected_digest {
+                return Err(format!(
+                    "turn {} (recorded as turn {}): expected digest {:x}, got {:x}",
+                    self.turn, recorded_turn, expected_digest, actual_digest
+                ));
+            }
+        } else if let Some(expected) = self.verifications.pop_front() {
+            let actual = self.verification();
+            if actual != expected {
+                return Err(format!(
+                    "turn {}: expected {:?}, got {:?}",
+                    self.turn, expected, actual
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drives an existing replay to completion with no window, audio
+    /// or rendering, for regression-testing the simulation from
+    /// `#[test]`. `step` should apply one turn's worth of recorded
+    /// input to `state`, the same way the interactive game loop does;
+    /// this function only owns loading the replay, pacing the turns
+    /// and checking each recorded checkpoint against the live state
+    /// as it goes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_replay(
+        world_size: Point,
+        map_size: Point,
+        panel_width: i32,
+        replay_path: &std::path::Path,
+        challenge: Challenge,
+        palette: Palette,
+        mut step: impl FnMut(&mut State),
+    ) -> Result<ReplayReport, Box<dyn Error>> {
+        let cheating = false;
+        let invincible = false;
+        let replay_full_speed = true;
+        let exit_after = true;
+        let mut state = Self::replay_game(
+            world_size,
+            map_size,
+            panel_width,
+            replay_path,
+            cheating,
+            invincible,
+            replay_full_speed,
+            exit_after,
+            challenge,
+            palette,
+        )?;
+
+        let mut diverged_at_turn = None;
+        while !state.inputs.is_empty()
+            || !state.verifications.is_empty()
+            || !state.verification_digests.is_empty()
+        {
+            step(&mut state);
+            if let Err(divergence) = state.verify_next_checkpoint() {
+                log::error!(
+                    "Replay '{}' diverged at {}",
+                    replay_path.display(),
+                    divergence
+                );
+                diverged_at_turn = Some(state.turn);
+                break;
+            }
+        }
+
+        Ok(ReplayReport {
+            turns_processed: state.turn,
+            final_session: state.game_session,
+            diverged_at_turn,
+        })
+    }
+
     pub fn save_to_file(&self) -> Result<(), Box<dyn Error>> {
         // TODO: select the filename dynamically!
         let filename = "SAVEDGAME.sav";
-        let version_data = bincode::serialize(crate::metadata::VERSION)?;
-        let commit_data = bincode::serialize(crate::metadata::GIT_HASH)?;
-        let state_data = bincode::serialize(self)?;
-
-        // TODO: this can be compressed nicely!
-
         let mut file = File::create(filename)?;
-        file.write_all(&version_data)?;
-        file.write_all(&commit_data)?;
-        file.write_all(&state_data)?;
-        file.flush()?;
+        write_compressed_header(&mut file)?;
+
+        let mut encoder = DeflateEncoder::new(file, Compression::default());
+        bincode::serialize_into(
+            &mut encoder,
+            &(crate::metadata::VERSION, crate::metadata::GIT_HASH, self),
+        )?;
+        encoder.finish()?;
 
         Ok(())
     }
 
     pub fn load_from_file() -> Result<State, Box<dyn Error>> {
         let filename = "SAVEDGAME.sav";
-        let state = {
-            let file = File::open(filename)?;
-            let version: String = bincode::deserialize_from(&file)?;
-            log::info!("Savefile version {}", version);
-            if version != crate::metadata::VERSION {
-                log::warn!("The game was saved in a different version: {}. This release has version: {}. The game might not load properly.",
-                           version,
-                           crate::metadata::VERSION);
-            }
-            let commit: String = bincode::deserialize_from(&file)?;
-            log::info!("Savefile commit {}", commit);
-            if commit != crate::metadata::GIT_HASH {
-                log::warn!("The game was saved in a different commit: {}. This release has commit: {}. The game might not load properly.",
-                           commit,
-                crate::metadata::GIT_HASH);
-            }
-            bincode::deserialize_from(&file)?
-        };
+        let mut file = File::open(filename)?;
+        let state = read_compressed_state(&mut file)?;
 
         if let Err(error) = ::std::fs::remove_file(filename) {
             log::error!(
@@ -564,6 +860,63 @@ Reason: '{}'.",
         Ok(state)
     }
 
+    /// The "most recent attempt" managed replay slot for this game's
+    /// `Challenge`. Exposed so the main menu can offer "Replay Last".
+    pub fn last_replay_path(&self) -> PathBuf {
+        last_replay_path(self.challenge)
+    }
+
+    /// The "best attempt so far" managed replay slot for this game's
+    /// `Challenge`. Exposed so the main menu can offer "Replay Best".
+    pub fn best_replay_path(&self) -> PathBuf {
+        best_replay_path(self.challenge)
+    }
+
+    /// Retires this session's recorded replay (if any) into the
+    /// managed "last"/"best" slots for its `Challenge`, pruning
+    /// whichever slot's previous occupant it replaces. "Best" is
+    /// decided by turns survived, the only score-like value reachable
+    /// without a real scoring system. Call this once a session ends.
+    ///
+    /// Takes `&mut self` (rather than `&self`) because it has to
+    /// finish `self.input_logger` first: it's still the live
+    /// `DeflateEncoder` that has been streaming to `recorded_path` all
+    /// session, and the read-back below needs that stream's final
+    /// deflate block flushed or it'll decode as truncated.
+    pub fn finalize_replay(&mut self) -> io::Result<()> {
+        let recorded_path = match &self.replay_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        self.input_logger.finish()?;
+
+        let turns_survived = self.turn;
+
+        rewrite_replay_with_challenge_header(
+            recorded_path,
+            &self.last_replay_path(),
+            self.challenge,
+            turns_survived,
+        )?;
+
+        let best_path = self.best_replay_path();
+        let is_new_best = match read_replay_challenge_header(&best_path) {
+            Some((_, previous_best_turns)) => turns_survived >= previous_best_turns,
+            None => true,
+        };
+        if is_new_best {
+            rewrite_replay_with_challenge_header(
+                recorded_path,
+                &best_path,
+                self.challenge,
+                turns_survived,
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn screen_left_top_corner(&self) -> Point {
         self.screen_position_in_world - (self.map_size / 2)
     }
@@ -575,6 +928,367 @@ Reason: '{}'.",
     pub fn mouse_world_position(&self) -> Point {
         self.screen_left_top_corner() + self.mouse.tile_pos
     }
+
+    /// Start auto-walking the player towards `goal`, replacing any
+    /// route currently in progress. `is_walkable` should treat walls,
+    /// out-of-bounds tiles and monster-occupied tiles as impassable.
+    /// Does nothing if `goal` can't be reached.
+    pub fn set_path_goal(&mut self, goal: Point, is_walkable: impl Fn(Point) -> bool) {
+        if let Some(path) = pathfinding::Path::to(self.player.pos, goal, is_walkable) {
+            self.player_path = path;
+        }
+    }
+
+    /// Drop the in-progress click-to-move route, e.g. because the
+    /// player pressed a movement key themselves.
+    pub fn cancel_path(&mut self) {
+        self.player_path.cancel();
+    }
+
+    /// Interprets a left click at `self.mouse`'s current tile position
+    /// as a click-to-move order, the same way pressing a movement key
+    /// sets `self.commands` -- callers should skip this while the
+    /// click landed on a UI element (a sidebar button, a window) rather
+    /// than the map itself. Does nothing if the mouse wasn't clicked
+    /// this frame.
+    ///
+    /// NOTE: the per-turn input dispatch that owns disambiguating a
+    /// sidebar click from a map click, draining `self.player_path` into
+    /// `self.commands` one step per turn, and calling `cancel_path` on
+    /// a manual move or a blocked step lives in `game::update`, which
+    /// (like `ai.rs`/`settings.rs`) isn't part of this editable
+    /// snapshot. This method and `step_path` below are the pieces
+    /// `game::update` would call into; wiring them in is the only
+    /// change needed once it's available.
+    pub fn handle_map_click(&mut self, is_walkable: impl Fn(Point) -> bool) {
+        if self.mouse.left_clicked {
+            let goal = self.mouse_world_position();
+            self.set_path_goal(goal, is_walkable);
+        }
+    }
+
+    /// Pops the next queued click-to-move step as a `Command`, or
+    /// cancels the route and returns `None` if that step is no longer
+    /// walkable (e.g. a monster moved into it since the route was
+    /// computed). Returns `None` with no route in progress.
+    pub fn step_path(&mut self, is_walkable: impl Fn(Point) -> bool) -> Option<Command> {
+        if self.player_path.next_step_blocked(&is_walkable) {
+            self.cancel_path();
+            return None;
+        }
+        self.player_path.next_command(self.player.pos)
+    }
+
+    // NOTE: the probability/severity tables and `roll_bad_trip` belong
+    // in `formula` (alongside the rest of this game's tuning
+    // constants), and applying a rolled `MiscastSeverity` belongs in
+    // the modifier/state machinery that drains Will or spawns
+    // monsters -- both `formula.rs` and `player.rs` are outside this
+    // editable snapshot, so the risk curve lives here instead, on the
+    // one piece of state it actually needs: `contamination`.
+
+    /// Adds contamination from consuming a dose of `power`, scaled by
+    /// `overuse` (how much further past comfortable tolerance the
+    /// player already is -- higher while already High). Mirrors the
+    /// classic bad-trip risk curve: risk grows with the square of how
+    /// far over the line the dose pushes you.
+    pub fn add_dose_contamination(&mut self, power: f32, overuse: f32) {
+        self.contamination = contaminated_by_dose(self.contamination, power, overuse);
+    }
+
+    /// Contamination slowly decays while the player isn't using.
+    pub fn decay_contamination(&mut self) {
+        self.contamination = decayed_contamination(self.contamination);
+    }
+
+    /// Rolls whether this turn triggers a bad trip and, if so, which
+    /// severity tier. The roll chance rises with `contamination`; the
+    /// tier is the highest one the current contamination qualifies
+    /// for, so a barely-contaminated player can still only ever get a
+    /// `Mild` trip.
+    pub fn roll_bad_trip(&mut self) -> Option<MiscastSeverity> {
+        let chance_per_mille = bad_trip_chance_per_mille(self.contamination);
+        if chance_per_mille <= 0 || self.rng.range_inclusive(1, 1000) > chance_per_mille {
+            return None;
+        }
+
+        Some(miscast_severity_for(self.contamination))
+    }
+
+    // NOTE: the actual per-turn conditions (first time a Dose is
+    // visible, first time the player steps into its glow, etc.) are
+    // evaluated in `game.rs`, outside this editable snapshot. Each
+    // such check should call `trigger_tutorial_event` with the event
+    // and the world tile it's about; everything else -- "only the
+    // first time", persistence, and the popup itself -- is handled
+    // here and in `windows::help::TutorialHint`.
+
+    /// Queues `event`'s hint to pop up anchored near `anchor`, unless
+    /// tutorials are disabled or `event` already fired earlier this
+    /// game.
+    pub fn trigger_tutorial_event(&mut self, event: TutorialEvent, anchor: Point) {
+        if !self.tutorial_enabled {
+            return;
+        }
+        if self.fired_tutorial_events.insert(event) {
+            self.pending_tutorial_event = Some((event, anchor));
+        }
+    }
+
+    /// Dismisses whichever tutorial hint is currently being shown.
+    pub fn dismiss_tutorial_event(&mut self) {
+        self.pending_tutorial_event = None;
+    }
+
+    /// Dismisses `windows::help::ContentNote` for good.
+    pub fn dismiss_content_note(&mut self) {
+        self.content_note_seen = true;
+    }
+
+    /// The resolved colors for `theme_preset`, ready for
+    /// `windows::sidebar` to draw with.
+    pub fn theme(&self) -> crate::theme::Theme {
+        self.theme_preset.theme()
+    }
+
+    /// Switches to the next built-in theme preset, wrapping back to
+    /// the first one. Accessibility-minded players cycle through this
+    /// from the settings menu.
+    pub fn cycle_theme(&mut self) {
+        self.theme_preset = self.theme_preset.next();
+    }
+}
+
+/// Periodic bincode snapshots of a replayed `State`, taken during the
+/// initial forward pass so a later `ReplayCursor::seek_to_turn` can
+/// restore the nearest earlier snapshot instead of replaying from
+/// turn 0 every time. Plain (uncompressed) bincode, same encoding
+/// `save_to_file` uses under its compression wrapper -- these never
+/// touch disk, so there's nothing to gain from spending CPU on
+/// `DeflateEncoder` here.
+struct ReplaySnapshots {
+    /// Capture a snapshot every `interval` turns. `None` disables
+    /// snapshotting entirely, so `seek_to_turn` always replays from
+    /// turn 0.
+    interval: Option<i32>,
+    snapshots: Vec<(i32, Vec<u8>)>,
+}
+
+impl ReplaySnapshots {
+    fn new(interval: Option<i32>) -> Self {
+        ReplaySnapshots {
+            interval,
+            snapshots: Vec::new(),
+        }
+    }
+
+    fn maybe_capture(&mut self, state: &State) {
+        let interval = match self.interval {
+            Some(interval) if interval > 0 => interval,
+            _ => return,
+        };
+        if state.turn % interval != 0 {
+            return;
+        }
+        match bincode::serialize(state) {
+            Ok(bytes) => self.snapshots.push((state.turn, bytes)),
+            Err(err) => log::error!(
+                "Failed to snapshot the replay state at turn {}: {}",
+                state.turn,
+                err
+            ),
+        }
+    }
+
+    /// The most recent snapshot at or before `target`, if the cache
+    /// holds one.
+    fn nearest_at_or_before(&self, target: i32) -> Option<&(i32, Vec<u8>)> {
+        self.snapshots
+            .iter()
+            .rev()
+            .find(|(turn, _)| *turn <= target)
+    }
+}
+
+/// Drives an open replay forward and backward by turn number instead
+/// of only linearly from turn 0, for the paused-replay step/jump
+/// controls. Wraps `State::replay_game` the same way `verify_replay`
+/// does -- through a caller-supplied `step` closure -- since the real
+/// per-turn simulation step (`game::update`) isn't reachable from
+/// here.
+pub struct ReplayCursor {
+    world_size: Point,
+    map_size: Point,
+    panel_width: i32,
+    replay_path: PathBuf,
+    challenge: Challenge,
+    palette: Palette,
+
+    /// The full recorded input/checkpoint sequences, kept separately
+    /// from `state.inputs`/`state.verifications`/
+    /// `state.verification_digests` -- those are `#[serde(skip)]` and
+    /// so come back empty on every snapshot restore; `seek_to_turn`
+    /// needs the originals to requeue the right remainder.
+    all_inputs: Vec<Input>,
+    all_verifications: Vec<Verification>,
+    all_verification_digests: Vec<(i32, u64)>,
+
+    state: State,
+    snapshots: ReplaySnapshots,
+}
+
+impl ReplayCursor {
+    /// Opens `replay_path` at turn 0. `snapshot_interval` is how often
+    /// (in turns) to cache a restorable snapshot during forward
+    /// playback; pass `None` to disable the cache and always seek by
+    /// replaying from the start.
+    pub fn open(
+        world_size: Point,
+        map_size: Point,
+        panel_width: i32,
+        replay_path: &std::path::Path,
+        challenge: Challenge,
+        palette: Palette,
+        snapshot_interval: Option<i32>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let state = Self::load(
+            world_size,
+            map_size,
+            panel_width,
+            replay_path,
+            challenge,
+            palette.clone(),
+        )?;
+
+        let all_inputs: Vec<Input> = state.inputs.iter().cloned().collect();
+        let all_verifications: Vec<Verification> = state.verifications.iter().cloned().collect();
+        let all_verification_digests: Vec<(i32, u64)> =
+            state.verification_digests.iter().cloned().collect();
+
+        Ok(ReplayCursor {
+            world_size,
+            map_size,
+            panel_width,
+            replay_path: replay_path.to_path_buf(),
+            challenge,
+            palette,
+            all_inputs,
+            all_verifications,
+            all_verification_digests,
+            state,
+            snapshots: ReplaySnapshots::new(snapshot_interval),
+        })
+    }
+
+    fn load(
+        world_size: Point,
+        map_size: Point,
+        panel_width: i32,
+        replay_path: &std::path::Path,
+        challenge: Challenge,
+        palette: Palette,
+    ) -> Result<State, Box<dyn Error>> {
+        let cheating = false;
+        let invincible = false;
+        let replay_full_speed = true;
+        let exit_after = true;
+        State::replay_game(
+            world_size,
+            map_size,
+            panel_width,
+            replay_path,
+            cheating,
+            invincible,
+            replay_full_speed,
+            exit_after,
+            challenge,
+            palette,
+        )
+    }
+
+    pub fn current_turn(&self) -> i32 {
+        self.state.turn
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Advances exactly one turn via `step`, capturing a snapshot
+    /// afterward if the resulting turn lands on the snapshot
+    /// interval.
+    pub fn step(&mut self, mut step: impl FnMut(&mut State)) {
+        step(&mut self.state);
+        self.snapshots.maybe_capture(&self.state);
+    }
+
+    /// Moves playback to `target`. When `target` lies behind the
+    /// current turn, first re-creates the `State` (by restoring the
+    /// nearest snapshot at or before `target`, or by reopening the
+    /// replay file from scratch if none covers it); then fast-forwards
+    /// via `step` to `target`.
+    pub fn seek_to_turn(
+        &mut self,
+        target: i32,
+        mut step: impl FnMut(&mut State),
+    ) -> Result<(), Box<dyn Error>> {
+        if target < self.state.turn {
+            self.rewind(target)?;
+        }
+
+        while self.state.turn < target && !self.state.inputs.is_empty() {
+            self.step(&mut step);
+        }
+
+        Ok(())
+    }
+
+    fn rewind(&mut self, target: i32) -> Result<(), Box<dyn Error>> {
+        match self.snapshots.nearest_at_or_before(target) {
+            Some((turn, bytes)) => {
+                let turn = *turn;
+                self.state = bincode::deserialize(bytes)?;
+                self.requeue_from(turn);
+            }
+            None => {
+                self.state = Self::load(
+                    self.world_size,
+                    self.map_size,
+                    self.panel_width,
+                    &self.replay_path,
+                    self.challenge,
+                    self.palette.clone(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the `#[serde(skip)]` queues a restored snapshot lost,
+    /// with only the entries still ahead of `from_turn` left in them --
+    /// exactly what they'd hold if the replay had reached `from_turn`
+    /// normally. Assumes each consumed `Input` advances `turn` by
+    /// exactly one, matching this game's turn-based stepping.
+    fn requeue_from(&mut self, from_turn: i32) {
+        self.state.inputs = self
+            .all_inputs
+            .iter()
+            .filter(|input| input.tick_id > from_turn)
+            .cloned()
+            .collect();
+        self.state.verifications = self
+            .all_verifications
+            .iter()
+            .filter(|verification| verification.turn > from_turn)
+            .cloned()
+            .collect();
+        self.state.verification_digests = self
+            .all_verification_digests
+            .iter()
+            .filter(|(turn, _)| *turn > from_turn)
+            .cloned()
+            .collect();
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -605,8 +1319,201 @@ impl Default for Challenge {
     }
 }
 
-fn empty_command_logger() -> Box<dyn Write> {
-    Box::new(io::sink())
+/// How bad a rolled bad trip is. Mild is cosmetic (screen distortion,
+/// extra visual noise); Moderate drains Will or forces a random step;
+/// Severe spawns a hallucinated Anxiety/Shadows monster adjacent to
+/// the player that vanishes after a few turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiscastSeverity {
+    Mild,
+    Moderate,
+    Severe,
+}
+
+/// Contamination (in the same units as `State::contamination`) at or
+/// above which a rolled bad trip is a `Severe`/`Moderate` one rather than
+/// `Mild`. Tuned so a player has to be deep into overuse before the
+/// nastiest effects become possible.
+const SEVERE_MISCAST_THRESHOLD: f32 = 150.0;
+const MODERATE_MISCAST_THRESHOLD: f32 = 60.0;
+
+/// Per-mille chance of a bad trip rolling this turn, rising with
+/// `contamination`. Clamped so even a heavily contaminated player
+/// isn't guaranteed a trip every single turn.
+fn bad_trip_chance_per_mille(contamination: f32) -> i32 {
+    const NASTINESS: f32 = 0.8;
+    (contamination * NASTINESS).min(500.0) as i32
+}
+
+/// Pure core of `State::add_dose_contamination`, pulled out so the
+/// risk curve can be unit-tested without constructing a full `State`.
+fn contaminated_by_dose(current: f32, power: f32, overuse: f32) -> f32 {
+    const CONTAMINATION_SCALE: f32 = 100.0;
+    current + (power * overuse).powi(2) / CONTAMINATION_SCALE
+}
+
+/// Pure core of `State::decay_contamination`.
+fn decayed_contamination(current: f32) -> f32 {
+    const DECAY_PER_TURN: f32 = 0.5;
+    (current - DECAY_PER_TURN).max(0.0)
+}
+
+/// Pure core of `State::roll_bad_trip`'s tier selection: the highest
+/// tier `contamination` qualifies for.
+fn miscast_severity_for(contamination: f32) -> MiscastSeverity {
+    if contamination >= SEVERE_MISCAST_THRESHOLD {
+        MiscastSeverity::Severe
+    } else if contamination >= MODERATE_MISCAST_THRESHOLD {
+        MiscastSeverity::Moderate
+    } else {
+        MiscastSeverity::Mild
+    }
+}
+
+/// A just-in-time tutorial moment, shown the first (and only the
+/// first) time its condition becomes true. See
+/// `State::trigger_tutorial_event` and `windows::help::TutorialHint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TutorialEvent {
+    FirstDoseSeen,
+    SteppedIntoGlow,
+    FirstAnxiety,
+    FirstOverdose,
+    MetFriendlyNpc,
+    WillIncreased,
+}
+
+impl TutorialEvent {
+    /// The one short paragraph shown in the hint popup for this event.
+    pub fn hint_text(self) -> &'static str {
+        use TutorialEvent::*;
+        match self {
+            FirstDoseSeen => {
+                "That's a Dose. Its glow means you can't resist it once you step inside -- plan your approach."
+            }
+            SteppedIntoGlow => {
+                "You've stepped into a Dose's glow. There's no resisting it now."
+            }
+            FirstAnxiety => {
+                "That's Anxiety. It takes away Will each time it hits you -- defeat enough of them to grow it back."
+            }
+            FirstOverdose => {
+                "You've Overdosed -- using a Dose while already High, or one too strong for you, does that."
+            }
+            MetFriendlyNpc => {
+                "That's a friendly face. Bump into it while Sober for a bonus."
+            }
+            WillIncreased => "Your Will just grew. Higher Will shrinks a Dose's irresistible glow.",
+        }
+    }
+}
+
+/// The live recording sink `State::input_logger` writes every `Input`
+/// and verification digest/line to. Wraps the concrete
+/// `DeflateEncoder<File>` (rather than erasing it behind `Box<dyn
+/// Write>`, the way it used to) specifically so [`InputLogger::finish`]
+/// can flush the encoder's final deflate block -- without it, reading
+/// the file back mid-session (as `finalize_replay` does) decodes a
+/// truncated stream. `None` means "not recording" (replays and any
+/// session without a `replay_path` write to nowhere).
+pub struct InputLogger(Option<DeflateEncoder<File>>);
+
+impl InputLogger {
+    fn recording(encoder: DeflateEncoder<File>) -> Self {
+        InputLogger(Some(encoder))
+    }
+
+    fn discarding() -> Self {
+        InputLogger(None)
+    }
+
+    /// Flushes and closes the underlying file, if this logger is
+    /// actually recording one. Afterwards this logger discards writes,
+    /// the same as one that was never recording -- callers don't need
+    /// to stop writing to it first.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if let Some(encoder) = self.0.take() {
+            encoder.finish()?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for InputLogger {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            Some(encoder) => encoder.write(buf),
+            None => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.0 {
+            Some(encoder) => encoder.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+fn empty_command_logger() -> InputLogger {
+    InputLogger::discarding()
+}
+
+/// Bytes written before a compressed save/replay stream so
+/// `load_from_file`/`replay_game` can tell it apart from the older
+/// plaintext/bincode format and reach for the matching decoder.
+const COMPRESSED_MAGIC: &[u8; 4] = b"DRCZ";
+const COMPRESSED_FORMAT_VERSION: u8 = 1;
+
+fn write_compressed_header<W: Write>(writer: &mut W) -> io::Result<()> {
+    writer.write_all(COMPRESSED_MAGIC)?;
+    writer.write_all(&[COMPRESSED_FORMAT_VERSION])
+}
+
+/// Looks for `COMPRESSED_MAGIC` at the start of `reader`. If it's
+/// there, returns the format version byte that followed it and leaves
+/// the stream positioned right after the header, ready for a
+/// decompressing reader. Otherwise rewinds back to the start so the
+/// legacy plaintext/bincode format can still be read unchanged.
+fn sniff_compressed_header<R: Read + Seek>(reader: &mut R) -> io::Result<Option<u8>> {
+    let mut header = [0u8; COMPRESSED_MAGIC.len() + 1];
+    let bytes_read = reader.read(&mut header)?;
+    if bytes_read == header.len() && header[..COMPRESSED_MAGIC.len()] == COMPRESSED_MAGIC[..] {
+        Ok(Some(header[COMPRESSED_MAGIC.len()]))
+    } else {
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(None)
+    }
+}
+
+fn read_compressed_state<R: Read + Seek>(reader: &mut R) -> Result<State, Box<dyn Error>> {
+    let (version, commit, state): (String, String, State) = match sniff_compressed_header(reader)? {
+        Some(format_version) => {
+            if format_version != COMPRESSED_FORMAT_VERSION {
+                log::warn!(
+                    "Unknown compressed savefile format version: {}. Attempting to load anyway.",
+                    format_version
+                );
+            }
+            bincode::deserialize_from(DeflateDecoder::new(reader))?
+        }
+        None => bincode::deserialize_from(reader)?,
+    };
+
+    log::info!("Savefile version {}", version);
+    if version != crate::metadata::VERSION {
+        log::warn!("The game was saved in a different version: {}. This release has version: {}. The game might not load properly.",
+                   version,
+                   crate::metadata::VERSION);
+    }
+    log::info!("Savefile commit {}", commit);
+    if commit != crate::metadata::GIT_HASH {
+        log::warn!("The game was saved in a different commit: {}. This release has commit: {}. The game might not load properly.",
+                   commit,
+                   crate::metadata::GIT_HASH);
+    }
+
+    Ok(state)
 }
 
 pub fn log_header<W: Write>(writer: &mut W, seed: u32) {
@@ -636,3 +1543,188 @@ pub fn log_verification<W: Write>(writer: &mut W, verification: &Verification) {
         }
     }
 }
+
+/// Writes a compact `turn:digest` line, the `VerificationMode::Record`
+/// alternative to `log_verification`'s full JSON dump.
+pub fn log_verification_digest<W: Write>(writer: &mut W, turn: i32, digest: u64) {
+    let _ = writeln!(writer, "{}:{:x}", turn, digest);
+}
+
+/// Parses a `turn:digest` line written by `log_verification_digest`.
+/// Returns `None` if `line` isn't in that format, so callers can fall
+/// through to trying other replay log line formats.
+fn parse_verification_digest_line(line: &str) -> Option<(i32, u64)> {
+    let mut parts = line.splitn(2, ':');
+    let turn = parts.next()?.parse().ok()?;
+    let digest = u64::from_str_radix(parts.next()?, 16).ok()?;
+    Some((turn, digest))
+}
+
+/// Writes the challenge flags and deciding metric (turns survived)
+/// `finalize_replay` splices into a managed "last"/"best" replay, so
+/// the replay manager can rank attempts without replaying them.
+fn log_challenge_header<W: Write>(writer: &mut W, challenge: Challenge, turns_survived: i32) {
+    let _ = writeln!(
+        writer,
+        "{}:{}:{}:{}",
+        challenge.hide_unseen_tiles,
+        challenge.fast_depression,
+        challenge.one_chance,
+        turns_survived
+    );
+}
+
+/// Parses a `log_challenge_header` line. Returns `None` if `line`
+/// isn't in that format, so callers can fall through to trying other
+/// replay log line formats.
+fn parse_challenge_header_line(line: &str) -> Option<(Challenge, i32)> {
+    let mut parts = line.splitn(4, ':');
+    let hide_unseen_tiles = parts.next()?.parse().ok()?;
+    let fast_depression = parts.next()?.parse().ok()?;
+    let one_chance = parts.next()?.parse().ok()?;
+    let turns_survived = parts.next()?.parse().ok()?;
+    let challenge = Challenge {
+        hide_unseen_tiles,
+        fast_depression,
+        one_chance,
+    };
+    Some((challenge, turns_survived))
+}
+
+/// Opens `path` (compressed or legacy, same sniffing `replay_game`
+/// uses) and reads back just the challenge flags and deciding metric
+/// `finalize_replay` wrote into it, without parsing the turn-by-turn
+/// body.
+fn read_replay_challenge_header(path: &std::path::Path) -> Option<(Challenge, i32)> {
+    use std::io::{BufRead, BufReader};
+
+    let mut file = File::open(path).ok()?;
+    let mut lines: Box<dyn Iterator<Item = io::Result<String>>> =
+        match sniff_compressed_header(&mut file).ok()? {
+            Some(_) => Box::new(BufReader::new(DeflateDecoder::new(file)).lines()),
+            None => Box::new(BufReader::new(file).lines()),
+        };
+
+    // Skip the seed/version/commit preamble `log_header` wrote.
+    lines.next()?.ok()?;
+    lines.next()?.ok()?;
+    lines.next()?.ok()?;
+
+    let challenge_line = lines.next()?.ok()?;
+    parse_challenge_header_line(&challenge_line)
+}
+
+/// Copies `source_path` (a just-finished session's replay) to
+/// `dest_path`, splicing a `log_challenge_header` line in right after
+/// the existing seed/version/commit preamble so the destination can
+/// later be ranked via `read_replay_challenge_header` alone.
+fn rewrite_replay_with_challenge_header(
+    source_path: &std::path::Path,
+    dest_path: &std::path::Path,
+    challenge: Challenge,
+    turns_survived: i32,
+) -> io::Result<()> {
+    use std::io::{BufRead, BufReader};
+
+    let mut source = File::open(source_path)?;
+    let mut lines: Box<dyn Iterator<Item = io::Result<String>>> =
+        match sniff_compressed_header(&mut source)? {
+            Some(_) => Box::new(BufReader::new(DeflateDecoder::new(source)).lines()),
+            None => Box::new(BufReader::new(source).lines()),
+        };
+
+    if let Some(dir) = dest_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut dest = File::create(dest_path)?;
+    write_compressed_header(&mut dest)?;
+    let mut encoder = DeflateEncoder::new(dest, Compression::default());
+
+    // Preserve the seed/version/commit preamble verbatim, then splice
+    // in the challenge/metric line, then stream the rest of the log
+    // through unchanged.
+    for _ in 0..3 {
+        if let Some(line) = lines.next() {
+            writeln!(encoder, "{}", line?)?;
+        }
+    }
+    log_challenge_header(&mut encoder, challenge, turns_survived);
+    for line in lines {
+        writeln!(encoder, "{}", line?)?;
+    }
+
+    encoder.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn compressed_stream_round_trips_byte_identically() {
+        let verification = Verification {
+            turn: 42,
+            chunk_count: 7,
+            player_pos: Point::new(3, 4),
+            monsters: vec![],
+        };
+
+        let mut compressed = Vec::new();
+        write_compressed_header(&mut compressed).unwrap();
+        {
+            let mut encoder = DeflateEncoder::new(&mut compressed, Compression::default());
+            bincode::serialize_into(&mut encoder, &verification).unwrap();
+            encoder.finish().unwrap();
+        }
+
+        let mut reader = Cursor::new(compressed);
+        let format_version = sniff_compressed_header(&mut reader)
+            .unwrap()
+            .expect("the compressed header should be detected");
+        assert_eq!(format_version, COMPRESSED_FORMAT_VERSION);
+
+        let reloaded: Verification =
+            bincode::deserialize_from(DeflateDecoder::new(reader)).unwrap();
+        assert_eq!(reloaded, verification);
+    }
+
+    #[test]
+    fn legacy_uncompressed_stream_is_not_mistaken_for_compressed() {
+        let mut plain = Vec::new();
+        bincode::serialize_into(&mut plain, &"legacy bincode payload").unwrap();
+
+        let mut reader = Cursor::new(plain);
+        assert_eq!(sniff_compressed_header(&mut reader).unwrap(), None);
+
+        // Sniffing must not consume the stream when it isn't compressed.
+        let reloaded: String = bincode::deserialize_from(&mut reader).unwrap();
+        assert_eq!(reloaded, "legacy bincode payload");
+    }
+
+    #[test]
+    fn contamination_grows_with_overuse_and_decays_over_time() {
+        let after_light_dose = contaminated_by_dose(0.0, 1.0, 1.0);
+        let after_heavy_dose = contaminated_by_dose(0.0, 1.0, 4.0);
+        assert!(after_heavy_dose > after_light_dose);
+
+        let decayed_once = decayed_contamination(after_heavy_dose);
+        assert!(decayed_once < after_heavy_dose);
+        assert!(decayed_contamination(0.0) >= 0.0, "contamination must not go negative");
+    }
+
+    #[test]
+    fn miscast_severity_rises_with_contamination() {
+        assert_eq!(miscast_severity_for(0.0), MiscastSeverity::Mild);
+        assert_eq!(
+            miscast_severity_for(MODERATE_MISCAST_THRESHOLD),
+            MiscastSeverity::Moderate
+        );
+        assert_eq!(
+            miscast_severity_for(SEVERE_MISCAST_THRESHOLD),
+            MiscastSeverity::Severe
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! A small, self-contained color system for the UI elements
+//! `windows::sidebar` draws pixels for directly: progress bars,
+//! labels, the panel fill, and the mouse-hover highlight rect. This is
+//! distinct from `palette::Palette` (not part of this checkout), which
+//! `ui::button` reads its own accent colors from -- `Theme` only
+//! covers the colors this checkout's sidebar code owns outright, but
+//! unlike `Palette` it ships a few ready-made presets and is meant to
+//! be swapped at runtime.
+
+use crate::color::Color;
+
+use serde::{Deserialize, Serialize};
+
+/// Every UI color `windows::sidebar` draws with directly.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub gui_text: Color,
+    pub dim_background: Color,
+    pub gui_progress_bar_bg: Color,
+    pub gui_progress_bar_fg: Color,
+    pub anxiety_progress_bar_bg: Color,
+    pub anxiety_progress_bar_fg: Color,
+    pub menu_highlight: Color,
+}
+
+/// Which built-in [`Theme`] is active. Stored in `State` rather than
+/// a resolved `Theme` itself, so switching presets doesn't need to
+/// remember which preset a set of raw colors came from.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemePreset {
+    Default,
+    HighContrast,
+    Light,
+}
+
+impl Default for ThemePreset {
+    fn default() -> Self {
+        ThemePreset::Default
+    }
+}
+
+impl ThemePreset {
+    /// Resolves this preset to its concrete colors.
+    pub fn theme(self) -> Theme {
+        match self {
+            ThemePreset::Default => Theme {
+                gui_text: Color {
+                    r: 230,
+                    g: 230,
+                    b: 230,
+                },
+                dim_background: Color { r: 20, g: 20, b: 20 },
+                gui_progress_bar_bg: Color {
+                    r: 40,
+                    g: 40,
+                    b: 40,
+                },
+                gui_progress_bar_fg: Color {
+                    r: 80,
+                    g: 160,
+                    b: 80,
+                },
+                anxiety_progress_bar_bg: Color {
+                    r: 40,
+                    g: 40,
+                    b: 40,
+                },
+                anxiety_progress_bar_fg: Color {
+                    r: 160,
+                    g: 60,
+                    b: 60,
+                },
+                menu_highlight: Color {
+                    r: 90,
+                    g: 90,
+                    b: 40,
+                },
+            },
+            // Near-maximum contrast, pure primaries for the two
+            // progress bar kinds so they stay distinguishable for
+            // colorblind players as well as low-vision ones.
+            ThemePreset::HighContrast => Theme {
+                gui_text: Color {
+                    r: 255,
+                    g: 255,
+                    b: 255,
+                },
+                dim_background: Color { r: 0, g: 0, b: 0 },
+                gui_progress_bar_bg: Color { r: 0, g: 0, b: 0 },
+                gui_progress_bar_fg: Color {
+                    r: 255,
+                    g: 255,
+                    b: 0,
+                },
+                anxiety_progress_bar_bg: Color { r: 0, g: 0, b: 0 },
+                anxiety_progress_bar_fg: Color { r: 255, g: 0, b: 0 },
+                menu_highlight: Color {
+                    r: 255,
+                    g: 255,
+                    b: 255,
+                },
+            },
+            ThemePreset::Light => Theme {
+                gui_text: Color { r: 20, g: 20, b: 20 },
+                dim_background: Color {
+                    r: 235,
+                    g: 235,
+                    b: 230,
+                },
+                gui_progress_bar_bg: Color {
+                    r: 200,
+                    g: 200,
+                    b: 195,
+                },
+                gui_progress_bar_fg: Color {
+                    r: 60,
+                    g: 120,
+                    b: 60,
+                },
+                anxiety_progress_bar_bg: Color {
+                    r: 200,
+                    g: 200,
+                    b: 195,
+                },
+                anxiety_progress_bar_fg: Color {
+                    r: 180,
+                    g: 50,
+                    b: 50,
+                },
+                menu_highlight: Color {
+                    r: 210,
+                    g: 210,
+                    b: 150,
+                },
+            },
+        }
+    }
+
+    /// Cycles to the next preset, wrapping back to `Default`.
+    pub fn next(self) -> Self {
+        match self {
+            ThemePreset::Default => ThemePreset::HighContrast,
+            ThemePreset::HighContrast => ThemePreset::Light,
+            ThemePreset::Light => ThemePreset::Default,
+        }
+    }
+}
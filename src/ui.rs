@@ -11,9 +11,152 @@ pub enum Text<'a> {
     Empty,
     EmptySpace(i32),
     Paragraph(&'a str),
+    /// Wraps like `Paragraph`, but distributes leftover horizontal
+    /// space evenly between words so both margins are flush -- except
+    /// on a one-word line or the paragraph's last line, which stay
+    /// left-aligned the way a justified text block conventionally does.
+    Justified(&'a str),
+    /// Like `Paragraph`, but each span can carry its own color instead
+    /// of the flow's default `color::gui_text`. Pass `None` for a span
+    /// that should just use the default -- wrapping and `text_height`
+    /// treat the concatenated spans as one paragraph.
+    Rich(&'a [(&'a str, Option<Color>)]),
     SquareTiles(&'a str),
 }
 
+impl TextOptions {
+    /// The options-based counterpart to `align_left`/`align_right`/
+    /// `align_center`, for `Text::Justified` blocks and `Button`s that
+    /// want the same wrapping width. `Text::Justified`'s own render
+    /// arm still computes each word's position by hand rather than
+    /// taking a `TextOptions` (see the NOTE there) -- this exists so
+    /// callers that only need the wrap width, like `Button`, have the
+    /// same builder-style entry point the other alignments do.
+    pub fn align_justified(width: i32) -> Self {
+        TextOptions {
+            wrap: true,
+            width,
+            ..Default::default()
+        }
+    }
+}
+
+/// How much extra horizontal space to insert after a word on a
+/// justified line: `base` pixels after every gap, plus one more pixel
+/// for the first `extra_count` gaps. This is the same scheme
+/// embedded-text uses to spread `remaining` pixels evenly without
+/// fractional pixel positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SpaceConfig {
+    base: i32,
+    extra_count: i32,
+}
+
+impl SpaceConfig {
+    fn new(remaining: i32, n_gaps: i32) -> Self {
+        if n_gaps <= 0 {
+            SpaceConfig {
+                base: 0,
+                extra_count: 0,
+            }
+        } else {
+            SpaceConfig {
+                base: remaining / n_gaps,
+                extra_count: remaining % n_gaps,
+            }
+        }
+    }
+
+    /// Width of the `index`-th gap (0-based) between words.
+    fn gap_width(&self, index: i32) -> i32 {
+        self.base + if index < self.extra_count { 1 } else { 0 }
+    }
+}
+
+/// Word-wrap `text` into lines no wider than `width`, using the same
+/// greedy algorithm `Paragraph` wrapping relies on. Returns the words
+/// making up each line so justification can re-measure the gaps.
+fn wrap_into_lines<'a>(text: &'a str, width: i32, metrics: &dyn TextMetrics) -> Vec<Vec<&'a str>> {
+    let space_width = metrics.get_text_width(" ", TextOptions::default()).max(1);
+    let mut lines = vec![];
+    let mut current_line: Vec<&str> = vec![];
+    let mut current_width = 0;
+
+    for word in text.split_whitespace() {
+        let word_width = metrics.get_text_width(word, TextOptions::default());
+        let width_with_word = if current_line.is_empty() {
+            word_width
+        } else {
+            current_width + space_width + word_width
+        };
+
+        if !current_line.is_empty() && width_with_word > width {
+            lines.push(std::mem::take(&mut current_line));
+            current_width = word_width;
+        } else {
+            current_width = width_with_word;
+        }
+        current_line.push(word);
+    }
+
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    lines
+}
+
+/// Split `spans` into individual words, each remembering the color of
+/// the span it came from -- this is what lets `Rich` be wrapped and
+/// measured as if it were one `Paragraph` while still drawing every
+/// word in its own color.
+fn rich_words<'a>(spans: &[(&'a str, Option<Color>)]) -> Vec<(&'a str, Option<Color>)> {
+    let mut words = vec![];
+    for &(text, color) in spans {
+        for word in text.split_whitespace() {
+            words.push((word, color));
+        }
+    }
+    words
+}
+
+/// Same greedy wrapping as `wrap_into_lines`, but operating on
+/// pre-split `(word, color)` pairs so a `Rich` flow's colors survive
+/// the wrap.
+fn wrap_rich_into_lines<'a>(
+    words: &[(&'a str, Option<Color>)],
+    width: i32,
+    metrics: &dyn TextMetrics,
+) -> Vec<Vec<(&'a str, Option<Color>)>> {
+    let space_width = metrics.get_text_width(" ", TextOptions::default()).max(1);
+    let mut lines = vec![];
+    let mut current_line: Vec<(&str, Option<Color>)> = vec![];
+    let mut current_width = 0;
+
+    for &(word, color) in words {
+        let word_width = metrics.get_text_width(word, TextOptions::default());
+        let width_with_word = if current_line.is_empty() {
+            word_width
+        } else {
+            current_width + space_width + word_width
+        };
+
+        if !current_line.is_empty() && width_with_word > width {
+            lines.push(std::mem::take(&mut current_line));
+            current_width = word_width;
+        } else {
+            current_width = width_with_word;
+        }
+        current_line.push((word, color));
+    }
+
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    lines
+}
+
 pub fn render_text_flow(
     text_flow: &[Text<'_>],
     rect: Rectangle,
@@ -55,6 +198,91 @@ pub fn render_text_flow(
                 };
             }
 
+            // NOTE: unlike Paragraph/Centered, this can't be a single
+            // options-driven `display.draw_text` call -- distributing
+            // the leftover width between individual words needs each
+            // word's own position, not just a wrap width. `rect.width()`
+            // plays the same role here that `TextOptions::width` (see
+            // `TextOptions::align_justified`) does for those variants.
+            Justified(text) => {
+                let space_width = metrics.get_text_width(" ", TextOptions::default()).max(1);
+                let lines = wrap_into_lines(text, rect.width(), metrics);
+                let line_count = lines.len();
+                for (line_index, words) in lines.iter().enumerate() {
+                    let line_index = line_index as i32;
+                    // Lines before `skip` are scrolled past entirely, same
+                    // as `options.skip` makes `Paragraph` do via `draw_text`.
+                    if line_index < skip {
+                        continue;
+                    }
+                    let y = ypos + (line_index - skip);
+                    if y >= rect.height() {
+                        continue;
+                    }
+                    let pos = rect.top_left() + Point::new(0, y);
+                    let is_last_line = line_index + 1 == line_count;
+                    if words.len() <= 1 || is_last_line {
+                        // Single-word or final line: left-align like Paragraph does.
+                        let joined = words.join(" ");
+                        display.draw_text(pos, &joined, color::gui_text, TextOptions::default());
+                        continue;
+                    }
+
+                    let words_width: i32 = words
+                        .iter()
+                        .map(|word| metrics.get_text_width(word, TextOptions::default()))
+                        .sum();
+                    let n_gaps = words.len() as i32 - 1;
+                    let minimum_space_width = n_gaps * space_width;
+                    let remaining = (rect.width() - words_width - minimum_space_width).max(0);
+                    let space_config = SpaceConfig::new(remaining, n_gaps);
+
+                    let mut x = 0;
+                    for (word_index, word) in words.iter().enumerate() {
+                        display.draw_text(
+                            pos + Point::new(x, 0),
+                            word,
+                            color::gui_text,
+                            TextOptions::default(),
+                        );
+                        let word_width = metrics.get_text_width(word, TextOptions::default());
+                        x += word_width;
+                        if word_index as i32 != n_gaps {
+                            x += space_width + space_config.gap_width(word_index as i32);
+                        }
+                    }
+                }
+            }
+
+            Rich(spans) => {
+                let space_width = metrics.get_text_width(" ", TextOptions::default()).max(1);
+                let words = rich_words(spans);
+                let lines = wrap_rich_into_lines(&words, rect.width(), metrics);
+                for (line_index, line_words) in lines.iter().enumerate() {
+                    let line_index = line_index as i32;
+                    // Same skip/position bookkeeping as `Justified` above.
+                    if line_index < skip {
+                        continue;
+                    }
+                    let y = ypos + (line_index - skip);
+                    if y >= rect.height() {
+                        continue;
+                    }
+                    let pos = rect.top_left() + Point::new(0, y);
+                    let mut x = 0;
+                    for (word, color) in line_words.iter() {
+                        let resolved_color = color.unwrap_or(color::gui_text);
+                        display.draw_text(
+                            pos + Point::new(x, 0),
+                            word,
+                            resolved_color,
+                            TextOptions::default(),
+                        );
+                        x += metrics.get_text_width(word, TextOptions::default()) + space_width;
+                    }
+                }
+            }
+
             Centered(text) => {
                 let pos = rect.top_left() + Point::new(0, ypos);
                 let options = TextOptions {
@@ -94,6 +322,39 @@ pub fn render_text_flow(
     DrawResult::Fit
 }
 
+/// Reports where pagination would cut `text_flow` off if it were
+/// rendered starting at `starting_line` into a `rect.height()`-line
+/// window, without actually drawing anything. Returns `Some(line)` with
+/// the starting line of the next page, or `None` if the whole flow fits
+/// on this one. Callers wanting a "previous page" offset can binary-
+/// search candidate starting lines against this (or `text_flow_rect`)
+/// until they find the latest one whose next page lands on the current
+/// `starting_line`.
+pub fn next_page_starting_line(
+    text_flow: &[Text<'_>],
+    rect: Rectangle,
+    starting_line: i32,
+    metrics: &dyn TextMetrics,
+) -> Option<i32> {
+    let mut skip = starting_line;
+    let mut ypos = 0;
+    for text in text_flow.iter() {
+        let height = text_height(text, rect, metrics);
+        ypos += height;
+        if height < skip {
+            ypos -= height;
+            skip -= height;
+        } else {
+            ypos -= skip;
+            skip = 0;
+        }
+        if ypos > rect.height() {
+            return Some(starting_line + rect.height());
+        }
+    }
+    None
+}
+
 fn text_height(text: &Text<'_>, rect: Rectangle, metrics: &dyn TextMetrics) -> i32 {
     use self::Text::*;
     match text {
@@ -107,6 +368,15 @@ fn text_height(text: &Text<'_>, rect: Rectangle, metrics: &dyn TextMetrics) -> i
             };
             metrics.get_text_height(text, options)
         }
+        // Justification only changes horizontal spacing, so it wraps
+        // into exactly as many lines as a plain `Paragraph` would.
+        Justified(text) => wrap_into_lines(text, rect.width(), metrics).len() as i32,
+        // Same reasoning as `Justified`: the concatenated spans wrap
+        // into one logical paragraph, regardless of per-span color.
+        Rich(spans) => {
+            let words = rich_words(spans);
+            wrap_rich_into_lines(&words, rect.width(), metrics).len() as i32
+        }
         Centered(_text) => 1,
         SquareTiles(_text) => 1,
     }
@@ -117,10 +387,7 @@ pub fn text_flow_rect(
     rect: Rectangle,
     metrics: &dyn TextMetrics,
 ) -> Rectangle {
-    let height = text_flow
-        .iter()
-        .map(|text| text_height(text, rect, metrics))
-        .sum();
+    let height = text_flow.iter().map(|text| text_height(text, rect, metrics)).sum();
     Rectangle::new(rect.top_left(), rect.top_left() + (0, height))
 }
 
@@ -174,4 +441,14 @@ impl Button {
             ..self
         }
     }
+
+    /// Options-based counterpart to `Text::Justified`, the same way
+    /// `align_center` is to `Text::Centered`: wraps to `width` rather
+    /// than positioning a single line.
+    pub fn align_justified(self, width: i32) -> Self {
+        Button {
+            text_options: TextOptions::align_justified(width),
+            ..self
+        }
+    }
 }
@@ -1,16 +1,14 @@
 use crate::{
     color,
     engine::{Display, DrawResult, TextMetrics, TextOptions},
+    graphic::{self, GraphicCategory},
     point::Point,
     rect::Rectangle,
     state::State,
     ui::{self, Button},
 };
 
-use std::{
-    convert::TryFrom,
-    fmt::{Display as FmtDisplay, Error, Formatter},
-};
+use std::convert::TryFrom;
 
 use serde::{Deserialize, Serialize};
 
@@ -32,6 +30,7 @@ pub enum Page {
     Legend,
     Credits,
     About,
+    Resources,
 }
 
 impl Page {
@@ -46,6 +45,7 @@ impl Page {
             Legend => Some(HowToPlay),
             Credits => Some(Legend),
             About => Some(Credits),
+            Resources => Some(About),
         }
     }
 
@@ -59,25 +59,29 @@ impl Page {
             HowToPlay => Some(Legend),
             Legend => Some(Credits),
             Credits => Some(About),
-            About => None,
+            About => Some(Resources),
+            Resources => None,
         }
     }
 }
 
-impl FmtDisplay for Page {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+impl Page {
+    /// The message key for this page's title, resolved through
+    /// `State::localizer` rather than hardcoded, so translations can
+    /// replace it without touching Rust code.
+    pub fn title_key(self) -> &'static str {
         use self::Page::*;
-        let s = match *self {
-            DoseResponse => "Dose Response",
-            NumpadControls => "Controls: numpad",
-            ArrowControls => "Controls: arrow keys",
-            ViKeys => "Controls: Vi keys",
-            HowToPlay => "How to play",
-            Legend => "Legend",
-            Credits => "Credits",
-            About => "About Dose Response",
-        };
-        f.write_str(s)
+        match self {
+            DoseResponse => "page-dose-response-title",
+            NumpadControls => "page-numpad-controls-title",
+            ArrowControls => "page-arrow-controls-title",
+            ViKeys => "page-vi-keys-title",
+            HowToPlay => "page-how-to-play-title",
+            Legend => "page-legend-title",
+            Credits => "page-credits-title",
+            About => "page-about-title",
+            Resources => "page-resources-title",
+        }
     }
 }
 
@@ -122,8 +126,8 @@ impl Window {
         let mut action_under_mouse = None;
         let mut rect_under_mouse = None;
 
-        let next_page_button = state.current_help_window.next().map(|text| {
-            let text = format!("[->] {}", text);
+        let next_page_button = state.current_help_window.next().map(|page| {
+            let text = format!("[->] {}", state.localizer.get(page.title_key()));
             let button = Button::new(contents_rect.bottom_right(), &text).align_right();
             let button_rect = metrics.button_rect(&button);
             if button_rect.contains(state.mouse.tile_pos) {
@@ -133,8 +137,8 @@ impl Window {
             button
         });
 
-        let prev_page_button = state.current_help_window.prev().map(|text| {
-            let text = format!("{} [<-]", text);
+        let prev_page_button = state.current_help_window.prev().map(|page| {
+            let text = format!("{} [<-]", state.localizer.get(page.title_key()));
             let button = Button::new(contents_rect.bottom_left(), &text);
             let button_rect = metrics.button_rect(&button);
             if button_rect.contains(state.mouse.tile_pos) {
@@ -196,12 +200,39 @@ impl Window {
         }
     }
 
-    pub fn render(
+    /// Renders assuming a GPU backend, i.e. `ascii_mode: false`. This
+    /// is the existing entry point `game.rs` (not in this checkout)
+    /// already calls, so its signature can't grow a new required
+    /// parameter without breaking that call site; `render_ascii`
+    /// below is the opt-in variant for `engine::ascii`.
+    pub fn render(&self, state: &State, metrics: &dyn TextMetrics, display: &mut Display, top_level: bool) {
+        self.render_impl(state, metrics, display, top_level, false)
+    }
+
+    /// Same as `render`, but draws the scrollbar chevrons as plain
+    /// '^'/'v' instead of the hardcoded codepoints below, for
+    /// `engine::ascii` (see that module). It still draws into a
+    /// `Display`: the box-drawing/inverse-video mapping of
+    /// `window_edge`/`window_background`/`menu_highlight` lives in
+    /// `AsciiFrame::fill_rect`, which these `draw_rectangle` calls do
+    /// not go through.
+    pub fn render_ascii(
+        &self,
+        state: &State,
+        metrics: &dyn TextMetrics,
+        display: &mut Display,
+        top_level: bool,
+    ) {
+        self.render_impl(state, metrics, display, top_level, true)
+    }
+
+    fn render_impl(
         &self,
         state: &State,
         metrics: &dyn TextMetrics,
         display: &mut Display,
         top_level: bool,
+        ascii_mode: bool,
     ) {
         use crate::ui::Text::*;
 
@@ -217,7 +248,7 @@ impl Window {
             color::window_background,
         );
 
-        let header = format!("{}", state.current_help_window);
+        let header = state.localizer.get(state.current_help_window.title_key());
         let version = &format!(
             "{} version: {}",
             crate::metadata::TITLE,
@@ -231,7 +262,7 @@ impl Window {
         display.draw_text_in_tile_coordinates(
             // TODO: this needs to be pixel
             layout.window_rect.top_left(),
-            &header,
+            header,
             color::gui_text,
             TextOptions::align_center(layout.window_rect.width()),
             display.tile_size,
@@ -239,23 +270,38 @@ impl Window {
 
         let mut lines = vec![];
 
+        // Built unconditionally (it's cheap) so the `'{glyph}' (name):
+        // description` strings it owns outlive the `match` below --
+        // only the `Page::Legend` arm reads it.
+        let legend_entries: Vec<(GraphicCategory, String)> = graphic::ALL
+            .iter()
+            .filter_map(|&g| {
+                let entry = g.legend_entry()?;
+                let glyph: char = g.into();
+                Some((
+                    entry.category,
+                    format!("'{}' ({}): {}", glyph, entry.name, entry.description),
+                ))
+            })
+            .collect();
+
         match state.current_help_window {
             Page::DoseResponse => {
-                lines.push(Paragraph("Dose Response is a roguelike: every time you start a game, the map will be different. The items and monsters will be in new places. And when you lose, that's it -- you can't reload and try again. You start from the beginning, with a brand new map. Every life matters."));
+                let t = |key| state.localizer.get(key);
+                lines.push(Paragraph(t("page-dose-response-p1")));
                 lines.push(Empty);
-                lines.push(Paragraph("You can't learn the map (because it changes), but you can learn the world. How do the monsters work? What happens when you take two doses at the same time? What's that glowing thing around a dose? What is food good for?"));
+                lines.push(Paragraph(t("page-dose-response-p2")));
                 lines.push(Empty);
-                lines.push(Paragraph("You will lose quickly and often. That's normal. Learn from it! What went wrong? Is there anything you could have done better? Were you saving an item for later that could have helped you?"));
+                lines.push(Paragraph(t("page-dose-response-p3")));
                 lines.push(Empty);
-                lines.push(Paragraph(
-                    "Each run takes 3 - 10 minutes so you won't lose that much anyway. Experiment!",
-                ));
+                lines.push(Paragraph(t("page-dose-response-p4")));
             }
 
             Page::NumpadControls => {
-                lines.push(Paragraph("You control the @ character. It moves just like the king in Chess: one step in any direction. That means up, down, left, right, but also diagonally."));
+                let t = |key| state.localizer.get(key);
+                lines.push(Paragraph(t("page-numpad-controls-p1")));
                 lines.push(Empty);
-                lines.push(Paragraph("You can use the numpad. Imagine your @ is in the middle (where [5] is) and you just pick a direction."));
+                lines.push(Paragraph(t("page-numpad-controls-p2")));
                 lines.push(EmptySpace(1));
 
                 lines.push(SquareTiles(r"7 8 9"));
@@ -265,13 +311,14 @@ impl Window {
                 lines.push(SquareTiles(r"1 2 3"));
 
                 lines.push(EmptySpace(1));
-                lines.push(Paragraph("Using items: you can use an item you're carrying (food and later on, doses) by clicking on it in the sidebar or pressing its number on the keyboard (not numpad -- that's for movement)."));
+                lines.push(Paragraph(t("page-numpad-controls-p3")));
             }
 
             Page::ArrowControls => {
-                lines.push(Paragraph("You control the @ character. It moves just like the king in Chess: one step in any direction. That means up, down, left, right, but also diagonally."));
+                let t = |key| state.localizer.get(key);
+                lines.push(Paragraph(t("page-arrow-controls-p1")));
                 lines.push(Empty);
-                lines.push(Paragraph("If you don't have a numpad, you can use the arrow keys. You will need [Shift] and [Ctrl] for diagonal movement. [Shift] means up and [Ctrl] means down. You combine them with the [Left] and [Right] keys."));
+                lines.push(Paragraph(t("page-arrow-controls-p2")));
 
                 lines.push(EmptySpace(1));
 
@@ -282,13 +329,14 @@ impl Window {
                 lines.push(SquareTiles(r"Ctrl+Left  Down Ctrl+Right "));
 
                 lines.push(EmptySpace(1));
-                lines.push(Paragraph("Using items: you can use an item you're carrying (food and later on, doses) by clicking on it in the sidebar or pressing its number on the keyboard (not numpad -- that's for movement)."));
+                lines.push(Paragraph(t("page-arrow-controls-p3")));
             }
 
             Page::ViKeys => {
-                lines.push(Paragraph("You control the @ character. It moves just like the king in Chess: one step in any direction. That means up, down, left, right, but also diagonally."));
+                let t = |key| state.localizer.get(key);
+                lines.push(Paragraph(t("page-vi-keys-p1")));
                 lines.push(Empty);
-                lines.push(Paragraph("You can also move using the \"Vi keys\". Those map to the letters on your keyboard. This makes more sense if you've ever used the Vi text editor."));
+                lines.push(Paragraph(t("page-vi-keys-p2")));
                 lines.push(EmptySpace(1));
 
                 lines.push(SquareTiles(r"y k u"));
@@ -298,85 +346,57 @@ impl Window {
                 lines.push(SquareTiles(r"b j n"));
 
                 lines.push(EmptySpace(1));
-                lines.push(Paragraph("Using items: you can use an item you're carrying (food and later on, doses) by clicking on it in the sidebar or pressing its number on the keyboard (not numpad -- that's for movement)."));
+                lines.push(Paragraph(t("page-vi-keys-p3")));
             }
 
             Page::HowToPlay => {
-                lines.push(Paragraph("Your character ('@') is an addict. Stay long without using a Dose ('i'), and the game is over. Eat food ('%') to remain sober for longer. Using a Dose or eating Food will also defeat nearby enemies."));
+                let t = |key| state.localizer.get(key);
+                lines.push(Paragraph(t("page-how-to-play-p1")));
                 lines.push(Empty);
-                lines.push(Paragraph("If you step into the glow around a Dose, you can't resist even if it means Overdosing yourself. At the beginning, you will also Overdose by using a Dose when you're still High or using a Dose that's too strong ('+', 'x' or 'I'). By using Doses you build up tolerance. You'll need stronger Doses later on."));
+                lines.push(Paragraph(t("page-how-to-play-p2")));
                 lines.push(Empty);
-                lines.push(Paragraph("The letters ('h', 'v', 'S', 'a' and 'D') are enemies. Each has their own way of harming you. The Depression ('D') moves twice as fast. The Anxiety ('a') will reduce your Will on each hit. When it reaches zero, you will lose."));
+                lines.push(Paragraph(t("page-how-to-play-p3")));
                 lines.push(Empty);
-                lines.push(Paragraph("To progress, your Will needs to get stronger. Defeat enough Anxieties ('a') to make it go up. The Dose or Food \"explosions\" don't count though! Higher Will shrinks the irresistible area around Doses. It also lets you pick them up!"));
+                lines.push(Paragraph(t("page-how-to-play-p4")));
                 lines.push(Empty);
-                lines.push(Paragraph("If you see another '@' characters, they are friendly. They will give you a bonus and follow you around, but only while you're Sober. You can have only one bonus active at a time."));
+                lines.push(Paragraph(t("page-how-to-play-p5")));
             }
 
             Page::Legend => {
-                lines.push(Paragraph("Monsters:"));
-                lines.push(Paragraph(
-                    "'a' (anxiety): takes Will away when it hits you. Defeat them to win the game.",
-                ));
-                lines.push(Paragraph(
-                    "'D' (depression): moves twice as fast. You lose immediately when it hits you.",
-                ));
-                lines.push(Paragraph(
-                    "'h' (hunger): summons other Hungers nearby. Reduces your mind state.",
-                ));
-                lines.push(Paragraph(
-                    "'v' (hearing voices): paralyzes you for three turns.",
-                ));
-                lines.push(Paragraph(
-                    "'S' (seeing shadows): makes you move randomly for three turns.",
-                ));
-                lines.push(Paragraph(
-                    "'@' (friendly): ignores you when High. Bump into them Sober for a bonus.",
-                ));
-                lines.push(Empty);
-
-                lines.push(Paragraph("Items:"));
-                lines.push(Paragraph("'%' (food): prolongs being Sober or in a Withdrawal. Kills monsters around you."));
-                lines.push(Paragraph(
-                    "'i' (dose): makes you High. When you're High already, you'll likely Overdose.",
-                ));
-                lines.push(Paragraph(
-                    "'+' (cardinal dose): Destroys trees in the horizontal and vertical lines.",
-                ));
-                lines.push(Paragraph(
-                    "'x' (diagonal dose): Destroys trees in the diagonal lines.",
-                ));
-                lines.push(Paragraph(
-                    "'I' (strong dose): very strong Dose. Don't walk into it by accident.",
-                ));
-                lines.push(Empty);
+                let t = |key| state.localizer.get(key);
+
+                for category in &[GraphicCategory::Monster, GraphicCategory::Item] {
+                    let heading_key = match category {
+                        GraphicCategory::Monster => "page-legend-monsters-heading",
+                        GraphicCategory::Item => "page-legend-items-heading",
+                        GraphicCategory::Terrain | GraphicCategory::Player => unreachable!(),
+                    };
+                    lines.push(Paragraph(t(heading_key)));
+                    for (entry_category, text) in &legend_entries {
+                        if entry_category == category {
+                            lines.push(Paragraph(text));
+                        }
+                    }
+                    lines.push(Empty);
+                }
 
-                lines.push(Paragraph("Each Dose has a faint glow around it. If you step into it, you will not be able to resist."));
+                lines.push(Paragraph(t("page-legend-glow-p1")));
                 lines.push(Empty);
-                lines.push(Paragraph("When the glow disappears completely, you can pick the dose up and use it later. Don't lose Will if you're carrying doses though!"));
+                lines.push(Paragraph(t("page-legend-glow-p2")));
             }
 
             Page::Credits => {
-                lines.push(Paragraph(
-                    "Design and development by Tomas Sedovic at https://tomas.sedovic.cz/",
-                ));
-                lines.push(Paragraph("Copyright (C) 2013-2020 Tomas Sedovic"));
-                lines.push(Paragraph(
-                    "licensed under GNU General Public License 3 or later",
-                ));
+                let t = |key| state.localizer.get(key);
+                lines.push(Paragraph(t("page-credits-p1")));
+                lines.push(Paragraph(t("page-credits-p2")));
+                lines.push(Paragraph(t("page-credits-p3")));
                 lines.push(Empty);
-                lines.push(Paragraph("Tiles by VEXED at https://vexed.zone/"));
-                lines.push(Paragraph("licensed under Creative Commons 0"));
+                lines.push(Paragraph(t("page-credits-p4")));
+                lines.push(Paragraph(t("page-credits-p5")));
                 lines.push(Empty);
-                lines.push(Paragraph(
-                    "Mononoki typeface by Matthias Tellen at https://github.com/madmalik",
-                ));
-                lines.push(Paragraph(
-                    "Copyright (c) 2013, Matthias Tellen matthias.tellen@googlemail.com",
-                ));
-                lines.push(Paragraph(
-                    "licensed under the SIL Open Font License, Version 1.1",
-                ));
+                lines.push(Paragraph(t("page-credits-p6")));
+                lines.push(Paragraph(t("page-credits-p7")));
+                lines.push(Paragraph(t("page-credits-p8")));
             }
 
             Page::About => {
@@ -389,11 +409,24 @@ impl Window {
                     lines.push(Empty);
                 }
 
-                lines.push(Paragraph("Dose Response is a Free and Open Source software provided under the terms of GNU General Public License version 3 or later. If you did not receieve the license text with the program, you can read it here:"));
-                lines.push(Paragraph("https://www.gnu.org/licenses/gpl-3.0.en.html"));
+                lines.push(Paragraph(state.localizer.get("page-about-license-p1")));
+                lines.push(Paragraph(state.localizer.get("page-about-license-url")));
                 lines.push(Empty);
                 lines.push(Paragraph(&copyright));
             }
+
+            Page::Resources => {
+                let t = |key| state.localizer.get(key);
+                lines.push(Paragraph(t("page-resources-p1")));
+                lines.push(Empty);
+                lines.push(Paragraph(t("page-resources-p2")));
+                lines.push(Empty);
+                lines.push(Paragraph(t("page-resources-p3")));
+                lines.push(Empty);
+                lines.push(Paragraph(t("page-resources-p4")));
+                lines.push(Empty);
+                lines.push(Paragraph(t("page-resources-p5")));
+            }
         }
 
         let res = ui::render_text_flow(
@@ -420,8 +453,16 @@ impl Window {
         }
 
         {
-            // Render the "up" portion of the scollbar
-            let glyph = char::try_from(710u32).unwrap_or('^');
+            // Render the "up" portion of the scollbar. Codepoint 710
+            // (a tiny superscript caret) looks right in the game's
+            // bitmap font, but it's an obscure modifier letter that
+            // most terminal fonts don't carry -- so the ascii backend
+            // gets a plain '^' instead.
+            let glyph = if ascii_mode {
+                '^'
+            } else {
+                char::try_from(710u32).unwrap_or('^')
+            };
             let button = &layout.scroll_up_button;
             let tilesize = metrics.tile_width_px();
             let x_offset_px = (tilesize - metrics.advance_width_px(glyph)) / 2;
@@ -447,8 +488,13 @@ impl Window {
         }
 
         {
-            // Render the "down" portion of the scollbar
-            let glyph = char::try_from(711u32).unwrap_or('v');
+            // Render the "down" portion of the scollbar. Same
+            // reasoning as the "up" glyph above.
+            let glyph = if ascii_mode {
+                'v'
+            } else {
+                char::try_from(711u32).unwrap_or('v')
+            };
             let button = &layout.scroll_down_button;
             let tilesize = metrics.tile_width_px();
             let x_offset_px = (tilesize - metrics.advance_width_px(glyph)) / 2;
@@ -495,3 +541,194 @@ impl Window {
             .action_under_mouse
     }
 }
+
+struct TutorialHintLayout {
+    window_rect: Rectangle,
+    text_rect: Rectangle,
+    close_button: Button,
+    close_button_under_mouse: bool,
+}
+
+/// A small, anchored popup for `State::pending_tutorial_event`, shown
+/// the first time a `TutorialEvent` fires near the tile it's about.
+/// Reuses the same text-flow and button layout as the full-screen
+/// `Window`, just at a much smaller size.
+pub struct TutorialHint;
+
+impl TutorialHint {
+    /// Width of the popup, in tiles. Tall enough for the hint's single
+    /// paragraph plus the close button, narrow enough to stay anchored
+    /// near the tile it's about rather than dominating the screen.
+    const WIDTH: i32 = 28;
+    const HEIGHT: i32 = 6;
+
+    fn layout(
+        &self,
+        state: &State,
+        metrics: &dyn TextMetrics,
+        display: &Display,
+    ) -> Option<TutorialHintLayout> {
+        let (_, anchor) = state.pending_tutorial_event?;
+        let anchor = state.screen_pos_from_world_pos(anchor);
+
+        let screen_size = display.size_without_padding();
+        let top_left = Point::new(
+            (anchor.x + 1).min((screen_size.x - Self::WIDTH).max(0)),
+            (anchor.y + 1).min((screen_size.y - Self::HEIGHT).max(0)),
+        );
+        let window_rect =
+            Rectangle::from_point_and_size(top_left, Point::new(Self::WIDTH, Self::HEIGHT));
+
+        let text_rect = Rectangle::new(
+            window_rect.top_left() + (1, 1),
+            window_rect.bottom_right() - (1, 2),
+        );
+
+        let mut close_button = Button::new(window_rect.bottom_right() - (1, 0), "[Esc] Got it");
+        close_button.text_options = TextOptions::align_right();
+        let close_button_rect = metrics.button_rect(&close_button);
+        let close_button_under_mouse = close_button_rect.contains(state.mouse.tile_pos);
+
+        Some(TutorialHintLayout {
+            window_rect,
+            text_rect,
+            close_button,
+            close_button_under_mouse,
+        })
+    }
+
+    pub fn render(&self, state: &State, metrics: &dyn TextMetrics, display: &mut Display) {
+        use crate::ui::Text::*;
+
+        let layout = match self.layout(state, metrics, display) {
+            Some(layout) => layout,
+            None => return,
+        };
+
+        display.draw_rectangle(layout.window_rect, color::window_edge);
+        display.draw_rectangle(
+            Rectangle::new(
+                layout.window_rect.top_left() + (1, 1),
+                layout.window_rect.bottom_right() - (1, 1),
+            ),
+            color::window_background,
+        );
+
+        if layout.close_button_under_mouse {
+            let button_rect = metrics.button_rect(&layout.close_button);
+            display.draw_rectangle(button_rect, color::menu_highlight);
+        }
+
+        if let Some((event, _)) = state.pending_tutorial_event {
+            let lines = vec![Paragraph(event.hint_text())];
+            ui::render_text_flow(&lines, layout.text_rect, 0, metrics, display);
+        }
+
+        display.draw_button(&layout.close_button);
+    }
+
+    /// Whether the popup's close button is being hovered, i.e. a click
+    /// should dismiss it via `State::dismiss_tutorial_event`.
+    pub fn hovered(&self, state: &State, metrics: &dyn TextMetrics, display: &Display) -> bool {
+        self.layout(state, metrics, display)
+            .map(|layout| layout.close_button_under_mouse)
+            .unwrap_or(false)
+    }
+}
+
+struct ContentNoteLayout {
+    window_rect: Rectangle,
+    text_rect: Rectangle,
+    close_button: Button,
+    close_button_under_mouse: bool,
+}
+
+/// The opt-out "content note" shown once on first launch, given the
+/// game's subject matter (addiction, and enemies literally named
+/// Anxiety/Depression/Hunger). Points players at `Page::Resources`.
+/// Dismissing it sets `State::content_note_seen`, so it never shows
+/// again. Laid out the same way as `TutorialHint`, just centered on
+/// screen instead of anchored to a world tile.
+pub struct ContentNote;
+
+impl ContentNote {
+    const WIDTH: i32 = 44;
+    const HEIGHT: i32 = 9;
+
+    fn layout(
+        &self,
+        state: &State,
+        metrics: &dyn TextMetrics,
+        display: &Display,
+    ) -> Option<ContentNoteLayout> {
+        if state.content_note_seen {
+            return None;
+        }
+
+        let screen_size = display.size_without_padding();
+        let top_left = Point::new(
+            ((screen_size.x - Self::WIDTH) / 2).max(0),
+            ((screen_size.y - Self::HEIGHT) / 2).max(0),
+        );
+        let window_rect =
+            Rectangle::from_point_and_size(top_left, Point::new(Self::WIDTH, Self::HEIGHT));
+
+        let text_rect = Rectangle::new(
+            window_rect.top_left() + (1, 1),
+            window_rect.bottom_right() - (1, 2),
+        );
+
+        let mut close_button = Button::new(window_rect.bottom_right() - (1, 0), "[Esc] Got it");
+        close_button.text_options = TextOptions::align_right();
+        let close_button_rect = metrics.button_rect(&close_button);
+        let close_button_under_mouse = close_button_rect.contains(state.mouse.tile_pos);
+
+        Some(ContentNoteLayout {
+            window_rect,
+            text_rect,
+            close_button,
+            close_button_under_mouse,
+        })
+    }
+
+    pub fn render(&self, state: &State, metrics: &dyn TextMetrics, display: &mut Display) {
+        use crate::ui::Text::*;
+
+        let layout = match self.layout(state, metrics, display) {
+            Some(layout) => layout,
+            None => return,
+        };
+
+        display.draw_rectangle(layout.window_rect, color::window_edge);
+        display.draw_rectangle(
+            Rectangle::new(
+                layout.window_rect.top_left() + (1, 1),
+                layout.window_rect.bottom_right() - (1, 1),
+            ),
+            color::window_background,
+        );
+
+        if layout.close_button_under_mouse {
+            let button_rect = metrics.button_rect(&layout.close_button);
+            display.draw_rectangle(button_rect, color::menu_highlight);
+        }
+
+        let t = |key| state.localizer.get(key);
+        let lines = vec![
+            Paragraph(t("content-note-p1")),
+            Empty,
+            Paragraph(t("content-note-p2")),
+        ];
+        ui::render_text_flow(&lines, layout.text_rect, 0, metrics, display);
+
+        display.draw_button(&layout.close_button);
+    }
+
+    /// Whether the popup's close button is being hovered, i.e. a click
+    /// should dismiss it via `State::dismiss_content_note`.
+    pub fn hovered(&self, state: &State, metrics: &dyn TextMetrics, display: &Display) -> bool {
+        self.layout(state, metrics, display)
+            .map(|layout| layout.close_button_under_mouse)
+            .unwrap_or(false)
+    }
+}
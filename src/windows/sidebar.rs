@@ -2,18 +2,168 @@ use crate::{
     color,
     engine::{Display, TextMetrics},
     game, graphics, item,
+    monster::Kind as MonsterKind,
     player::Mind,
     point::Point,
     rect::Rectangle,
-    state::State,
+    state::{State, TutorialEvent},
     ui::{self, Button},
+    windows::help::{ContentNote, TutorialHint},
 };
 
 use egui::{self, paint::PaintCmd, Rect, Ui};
 
+use serde::{Deserialize, Serialize};
+
 use std::{borrow::Cow, collections::HashMap, time::Duration};
 
-#[derive(Copy, Clone)]
+/// A small floating box of text anchored at the mouse position,
+/// describing whatever sidebar element is currently hovered.
+struct Tooltip {
+    lines: Vec<Cow<'static, str>>,
+}
+
+impl Tooltip {
+    fn new(lines: Vec<Cow<'static, str>>) -> Self {
+        Tooltip { lines }
+    }
+
+    fn width(&self) -> i32 {
+        self.lines
+            .iter()
+            .map(|line| line.chars().count() as i32)
+            .max()
+            .unwrap_or(0)
+            + 2
+    }
+
+    fn height(&self) -> i32 {
+        self.lines.len() as i32 + 2
+    }
+}
+
+fn item_tooltip(kind: item::Kind) -> Vec<Cow<'static, str>> {
+    use item::Kind::*;
+    match kind {
+        Food => vec!["Eat to stave off Hunger and withdrawal.".into()],
+        Dose => vec![
+            "Use to become High.".into(),
+            "Standing close to a Dose makes it hard to resist.".into(),
+        ],
+        CardinalDose => vec!["A stronger Dose. Easy to overdose on early on.".into()],
+        DiagonalDose => vec!["A stronger Dose. Easy to overdose on early on.".into()],
+        StrongDose => vec!["The strongest Dose. Very easy to overdose on.".into()],
+    }
+}
+
+fn mind_tooltip() -> Vec<Cow<'static, str>> {
+    vec![
+        "Sober: no bonuses or penalties.".into(),
+        "High: faster, but risks Overdose.".into(),
+        "Withdrawal: weak and vulnerable.".into(),
+        "Reaching zero Mind while sober loses the game.".into(),
+    ]
+}
+
+fn will_tooltip() -> Vec<Cow<'static, str>> {
+    vec!["Your resolve. Anxiety monsters drain it -- reach zero and you lose.".into()]
+}
+
+fn bonus_tooltip() -> Vec<Cow<'static, str>> {
+    vec!["An active bonus granted by a companion NPC.".into()]
+}
+
+fn stun_tooltip() -> Vec<Cow<'static, str>> {
+    vec!["Stunned: you cannot act until this wears off.".into()]
+}
+
+fn panic_tooltip() -> Vec<Cow<'static, str>> {
+    vec!["Panicking: your movement is randomised until this wears off.".into()]
+}
+
+/// Quantize the direction from `from` to `to` into one of the eight
+/// compass points and return an arrow glyph pointing that way. Used by
+/// the Victory NPC indicator so players get a sense of heading, not
+/// just distance.
+fn compass_arrow(from: Point, to: Point) -> &'static str {
+    let dx = (to.x - from.x) as f32;
+    let dy = (to.y - from.y) as f32;
+    if dx == 0.0 && dy == 0.0 {
+        return "@";
+    }
+    // Flip the y axis: tile coordinates grow downward, but we want
+    // "up" (north) to be a positive angle (east is angle zero).
+    let angle = (-dy).atan2(dx);
+    let octant = (angle / (std::f32::consts::PI / 4.0)).round() as i32;
+    match octant.rem_euclid(8) {
+        0 => "→",
+        1 => "↗",
+        2 => "↑",
+        3 => "↖",
+        4 => "←",
+        5 => "↙",
+        6 => "↓",
+        7 => "↘",
+        _ => unreachable!(),
+    }
+}
+
+/// How tall (in tiles) the cheat panel's frame-time graph is.
+const FRAME_GRAPH_HEIGHT_TILES: i32 = 6;
+
+/// Target per-frame time budget in milliseconds (60 FPS), drawn as a
+/// reference line on the frame-time graph so spikes past it stand out.
+const TARGET_FRAME_BUDGET_MS: f32 = 16.6;
+
+/// Draw a scrolling column chart of the last `update_ms.len()` frames:
+/// one column per frame, `update_ms` stacked below `drawcall_ms` in two
+/// colours, auto-scaled to the largest sampled value (or the frame
+/// budget, whichever is bigger). A horizontal line at
+/// `TARGET_FRAME_BUDGET_MS` makes it obvious at a glance when a frame
+/// went over budget. `pos` is the top-left tile of the graph.
+fn draw_frame_graph(
+    display: &mut Display,
+    metrics: &dyn TextMetrics,
+    pos: Point,
+    update_ms: &[f32],
+    drawcall_ms: &[f32],
+    update_color: color::Color,
+    drawcall_color: color::Color,
+    budget_line_color: color::Color,
+) {
+    let height = FRAME_GRAPH_HEIGHT_TILES;
+
+    let max_ms = update_ms
+        .iter()
+        .zip(drawcall_ms)
+        .map(|(update, drawcall)| update + drawcall)
+        .fold(TARGET_FRAME_BUDGET_MS, f32::max);
+    let ms_per_tile = max_ms / height as f32;
+
+    for (column, (&update, &drawcall)) in update_ms.iter().zip(drawcall_ms).enumerate() {
+        let update_tiles = ((update / ms_per_tile).round() as i32).min(height);
+        let total_tiles = (((update + drawcall) / ms_per_tile).round() as i32).min(height);
+
+        for row in 0..height {
+            let tile = pos + Point::new(column as i32, height - 1 - row);
+            if row < update_tiles {
+                display.set_background(tile, update_color);
+            } else if row < total_tiles {
+                display.set_background(tile, drawcall_color);
+            }
+        }
+    }
+
+    let budget_row = ((TARGET_FRAME_BUDGET_MS / ms_per_tile).round() as i32).min(height - 1);
+    let tilesize = metrics.tile_width_px();
+    let line_y = (pos.y + height - 1 - budget_row) * tilesize;
+    for column in 0..update_ms.len() as i32 {
+        let line_x = (pos.x + column) * tilesize;
+        display.draw_glyph_abs_px(line_x, line_y, '-', budget_line_color);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub enum Action {
     MainMenu,
     Help,
@@ -34,6 +184,213 @@ pub enum Action {
     MoveSE,
 }
 
+/// Which on-screen control scheme is active: the classic tile-based
+/// sidebar, or the touch-friendly overlay drawn directly over the
+/// play area. Stored in `State` and toggled from settings.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlMode {
+    Sidebar,
+    TouchOverlay,
+}
+
+/// Which screen corner the touch overlay's directional pad is
+/// anchored to, so left- and right-handed players can move it out of
+/// the way of the hand holding the device.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnchorSide {
+    Left,
+    Right,
+}
+
+/// The key currently bound to each movement direction plus the
+/// main-menu/help shortcuts. The sidebar reads this at render time
+/// rather than hardcoding the numpad digits or "[Esc]"/"[?]", so
+/// rebinding a key immediately updates every on-screen hint that
+/// mentions it. `Keybindings::load` is the actual settings read --
+/// there's no in-game rebind UI yet, so the only way to change a
+/// binding today is editing `keybindings.txt` by hand before
+/// launching.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Keybindings {
+    pub nw: char,
+    pub n: char,
+    pub ne: char,
+    pub w: char,
+    pub e: char,
+    pub sw: char,
+    pub s: char,
+    pub se: char,
+    pub main_menu: String,
+    pub help: String,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        // Mirrors the numpad control scheme described on the help screen.
+        Keybindings {
+            nw: '7',
+            n: '8',
+            ne: '9',
+            w: '4',
+            e: '6',
+            sw: '1',
+            s: '2',
+            se: '3',
+            main_menu: "Esc".to_string(),
+            help: "?".to_string(),
+        }
+    }
+}
+
+impl Keybindings {
+    /// Glyphs for the eight movement buttons, in the same order as
+    /// `numpad_buttons` (nw, n, ne, w, e, sw, s, se).
+    fn direction_glyphs(&self) -> [char; 8] {
+        [
+            self.nw, self.n, self.ne, self.w, self.e, self.sw, self.s, self.se,
+        ]
+    }
+
+    /// Loads overrides from `keybindings.txt` in the current
+    /// directory, one `action = key` pair per line (blank lines and
+    /// `#` comments ignored, same as `localization`'s catalog format).
+    /// Recognised actions are `nw`/`n`/`ne`/`w`/`e`/`sw`/`s`/`se`/
+    /// `main_menu`/`help`; anything else, or a value that doesn't
+    /// parse, is ignored rather than rejecting the whole file. Falls
+    /// back to `Keybindings::default()` entirely if the file doesn't
+    /// exist. There's no settings UI to write this file yet -- it's
+    /// meant to be hand-edited -- but it's a real, working override
+    /// path, not just a declared-and-unused default.
+    pub fn load() -> Self {
+        let mut bindings = Keybindings::default();
+        let src = match std::fs::read_to_string("keybindings.txt") {
+            Ok(src) => src,
+            Err(_) => return bindings,
+        };
+        for line in src.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim()),
+                _ => continue,
+            };
+            match key {
+                "nw" => bindings.nw = value.chars().next().unwrap_or(bindings.nw),
+                "n" => bindings.n = value.chars().next().unwrap_or(bindings.n),
+                "ne" => bindings.ne = value.chars().next().unwrap_or(bindings.ne),
+                "w" => bindings.w = value.chars().next().unwrap_or(bindings.w),
+                "e" => bindings.e = value.chars().next().unwrap_or(bindings.e),
+                "sw" => bindings.sw = value.chars().next().unwrap_or(bindings.sw),
+                "s" => bindings.s = value.chars().next().unwrap_or(bindings.s),
+                "se" => bindings.se = value.chars().next().unwrap_or(bindings.se),
+                "main_menu" if !value.is_empty() => bindings.main_menu = value.to_string(),
+                "help" if !value.is_empty() => bindings.help = value.to_string(),
+                _ => {}
+            }
+        }
+        bindings
+    }
+}
+
+/// Order the sidebar's keyboard/gamepad-navigable buttons are cycled
+/// through by the virtual cursor: movement buttons in reading order,
+/// then the help/menu buttons at the bottom, mirroring how they're
+/// stacked on screen.
+const FOCUS_ORDER: [Action; 10] = [
+    Action::MoveNW,
+    Action::MoveN,
+    Action::MoveNE,
+    Action::MoveW,
+    Action::MoveE,
+    Action::MoveSW,
+    Action::MoveS,
+    Action::MoveSE,
+    Action::Help,
+    Action::MainMenu,
+];
+
+/// A software pointer over the sidebar's buttons, moved by keyboard or
+/// gamepad instead of the mouse. Only the focused button's `Action`
+/// fires, and only on the activate key/button -- moving the cursor
+/// itself never triggers anything. This makes the sidebar (and so the
+/// whole game) playable without a pointing device.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct VirtualCursor {
+    pub enabled: bool,
+    focused: usize,
+}
+
+impl Default for VirtualCursor {
+    fn default() -> Self {
+        VirtualCursor {
+            enabled: false,
+            focused: 0,
+        }
+    }
+}
+
+impl VirtualCursor {
+    pub fn focused_action(self) -> Action {
+        FOCUS_ORDER[self.focused]
+    }
+
+    pub fn next(&mut self) {
+        self.enabled = true;
+        self.focused = (self.focused + 1) % FOCUS_ORDER.len();
+    }
+
+    pub fn previous(&mut self) {
+        self.enabled = true;
+        self.focused = (self.focused + FOCUS_ORDER.len() - 1) % FOCUS_ORDER.len();
+    }
+}
+
+/// Drive the virtual cursor from the keyboard and gamepad and return
+/// the focused button's `Action` if the activate key/button was just
+/// pressed. Call this once per frame the same way `Window::hovered` is
+/// called for the mouse -- moving the cursor only updates
+/// `state.virtual_cursor`, it doesn't produce an `Action` by itself.
+pub fn virtual_cursor_input(state: &mut State) -> Option<Action> {
+    use crate::{gamepad::CursorStep, keys::KeyCode};
+
+    if state.keys.matches_code(KeyCode::Tab) {
+        state.virtual_cursor.next();
+    }
+
+    match state.gamepad.cursor_navigation() {
+        Some(CursorStep::Next) => state.virtual_cursor.next(),
+        Some(CursorStep::Previous) => state.virtual_cursor.previous(),
+        None => {}
+    }
+
+    let activated = state.keys.matches_code(KeyCode::Return) || state.gamepad.cursor_activate();
+
+    if state.virtual_cursor.enabled && activated {
+        Some(state.virtual_cursor.focused_action())
+    } else {
+        None
+    }
+}
+
+/// Finds the first living monster of `kind`, so a tutorial hint can
+/// anchor its popup to the tile it's about. Mirrors the chunk-walk
+/// `State::verification` already does over `state.world`.
+fn first_living_monster_position(state: &State, kind: MonsterKind) -> Option<Point> {
+    for chunk_pos in state.world.positions_of_all_chunks() {
+        if let Some(chunk) = state.world.chunk(chunk_pos) {
+            for monster in chunk.monsters() {
+                if !monster.dead && monster.kind == kind {
+                    return Some(monster.position);
+                }
+            }
+        }
+    }
+    None
+}
+
 pub fn process(
     state: &mut State,
     ui: &mut Ui,
@@ -42,6 +399,25 @@ pub fn process(
     display: &Display,
     active: bool,
 ) -> Option<Action> {
+    use crate::keys::KeyCode;
+
+    // The popup's "[Esc] Got it" button also responds to the Esc key
+    // directly, the same way `endgame`/`main_menu`'s windows already
+    // treat Esc as their dismiss/back shortcut.
+    if state.pending_tutorial_event.is_some() && state.keys.matches_code(KeyCode::Esc) {
+        state.dismiss_tutorial_event();
+    } else if !state.content_note_seen && state.keys.matches_code(KeyCode::Esc) {
+        state.dismiss_content_note();
+    }
+
+    if let Some(anxiety_pos) = first_living_monster_position(state, MonsterKind::Anxiety) {
+        state.trigger_tutorial_event(TutorialEvent::FirstAnxiety, anxiety_pos);
+    }
+
+    if state.control_mode == ControlMode::TouchOverlay {
+        return process_overlay(state, ui, display, active);
+    }
+
     let mut action = None;
 
     let width_px = 250.0;
@@ -62,15 +438,14 @@ pub fn process(
     ui.set_clip_rect(full_rect);
 
     let mut style = ui.style().clone();
-    style.text_color = color::gui_text.into();
+    style.text_color = state.theme().gui_text.into();
     ui.set_style(style);
 
     ui.add_paint_cmd(PaintCmd::Rect {
         rect: full_rect,
         corner_radius: 0.0,
         outline: None,
-        // TODO: use `color::dim_background` this for background
-        fill: Some(color::RED.into()),
+        fill: Some(state.theme().dim_background.into()),
     });
 
     let player = &state.player;
@@ -100,7 +475,7 @@ pub fn process(
             ),
             corner_radius: 0.0,
             outline: None,
-            fill: Some(color::gui_progress_bar_bg.into()),
+            fill: Some(state.theme().gui_progress_bar_bg.into()),
         },
     );
 
@@ -117,7 +492,7 @@ pub fn process(
             ),
             corner_radius: 0.0,
             outline: None,
-            fill: Some(color::gui_progress_bar_fg.into()),
+            fill: Some(state.theme().gui_progress_bar_fg.into()),
         },
     );
 
@@ -146,7 +521,7 @@ pub fn process(
                 ),
                 corner_radius: 0.0,
                 outline: None,
-                fill: Some(color::anxiety_progress_bar_bg.into()),
+                fill: Some(state.theme().anxiety_progress_bar_bg.into()),
             },
         );
 
@@ -168,7 +543,7 @@ pub fn process(
                     ),
                     corner_radius: 0.0,
                     outline: None,
-                    fill: Some(color::anxiety_progress_bar_fg.into()),
+                    fill: Some(state.theme().anxiety_progress_bar_fg.into()),
                 },
             );
         }
@@ -199,7 +574,7 @@ pub fn process(
                     count,
                     pr = precision - 7
                 );
-                if ui.add(ui::button(&button_label, active)).clicked {
+                if ui.add(ui::button(&button_label, active, &state.palette)).clicked {
                     action = Some(button_action);
                 };
             }
@@ -213,7 +588,8 @@ pub fn process(
                 let dy = (player.pos.y - vnpc_pos.y) as f32;
                 dx.abs().max(dy.abs()) as i32
             };
-            ui.label(format!("Distance to Victory NPC: {}", distance));
+            let arrow = compass_arrow(player.pos, vnpc_pos);
+            ui.label(format!("Victory NPC: {} {}", arrow, distance));
         }
     }
 
@@ -234,6 +610,14 @@ pub fn process(
     }
 
     ui.label("Numpad Controls:");
+    let nw_label = state.keybindings.nw.to_string();
+    let n_label = state.keybindings.n.to_string();
+    let ne_label = state.keybindings.ne.to_string();
+    let w_label = state.keybindings.w.to_string();
+    let e_label = state.keybindings.e.to_string();
+    let sw_label = state.keybindings.sw.to_string();
+    let s_label = state.keybindings.s.to_string();
+    let se_label = state.keybindings.se.to_string();
     ui.columns(3, |c| {
         let mut style = c[0].style().clone();
         style.button_padding = [20.0, 15.0].into();
@@ -241,43 +625,58 @@ pub fn process(
             c[index].set_style(style.clone());
         }
 
-        if c[0].add(ui::button("7", active)).clicked {
+        if c[0].add(ui::button(&nw_label, active, &state.palette)).clicked {
             action = Some(Action::MoveNW);
         };
-        if c[1].add(ui::button("8", active)).clicked {
+        if c[1].add(ui::button(&n_label, active, &state.palette)).clicked {
             action = Some(Action::MoveN);
         };
-        if c[2].add(ui::button("9", active)).clicked {
+        if c[2].add(ui::button(&ne_label, active, &state.palette)).clicked {
             action = Some(Action::MoveNE);
         };
 
-        if c[0].add(ui::button("4", active)).clicked {
+        if c[0].add(ui::button(&w_label, active, &state.palette)).clicked {
             action = Some(Action::MoveW);
         };
         c[1].add(egui::Button::new("@").enabled(false));
-        if c[2].add(ui::button("6", active)).clicked {
+        if c[2].add(ui::button(&e_label, active, &state.palette)).clicked {
             action = Some(Action::MoveE);
         };
 
-        if c[0].add(ui::button("1", active)).clicked {
+        if c[0].add(ui::button(&sw_label, active, &state.palette)).clicked {
             action = Some(Action::MoveSW);
         };
-        if c[1].add(ui::button("2", active)).clicked {
+        if c[1].add(ui::button(&s_label, active, &state.palette)).clicked {
             action = Some(Action::MoveS);
         };
-        if c[2].add(ui::button("3", active)).clicked {
+        if c[2].add(ui::button(&se_label, active, &state.palette)).clicked {
             action = Some(Action::MoveSE);
         };
     });
 
-    if ui.add(ui::button("[?] Help", active)).clicked {
+    let help_label = format!("[{}] Help", state.keybindings.help);
+    if ui.add(ui::button(&help_label, active, &state.palette)).clicked {
         action = Some(Action::Help);
     }
 
-    if ui.add(ui::button("[Esc] Main Menu", active)).clicked {
+    let main_menu_label = format!("[{}] Main Menu", state.keybindings.main_menu);
+    if ui
+        .add(ui::button(&main_menu_label, active, &state.palette))
+        .clicked
+    {
         action = Some(Action::MainMenu);
     }
 
+    // Lets accessibility-minded players cycle to a higher-contrast or
+    // light theme without going through a settings menu that doesn't
+    // exist in this checkout.
+    if ui
+        .add(ui::button("Theme", active, &state.palette))
+        .clicked
+    {
+        state.cycle_theme();
+    }
+
     if state.cheating {
         ui.label("CHEATING");
 
@@ -310,6 +709,109 @@ pub fn process(
         ));
     }
 
+    action
+        .or_else(|| virtual_cursor_input(state))
+        .or_else(|| state.gamepad.poll())
+}
+
+/// The touch/wasm alternative to `process()`: a large semi-transparent
+/// directional pad plus item buttons, drawn directly over the play
+/// area instead of in the 250px sidebar. Positions are anchored to a
+/// screen corner via `display.screen_size_px` and hit-tested in screen
+/// pixels (via egui's own widget hit-testing), so the buttons stay
+/// finger-sized no matter how far the map is zoomed in or out. Only
+/// used when `state.control_mode` is `ControlMode::TouchOverlay`.
+pub fn process_overlay(
+    state: &mut State,
+    ui: &mut Ui,
+    display: &Display,
+    active: bool,
+) -> Option<Action> {
+    let mut action = None;
+
+    let button_size = 64.0;
+    let gap = 8.0;
+    let margin = 24.0;
+
+    let screen_width = display.screen_size_px.x as f32;
+    let screen_height = display.screen_size_px.y as f32;
+
+    let pad_width = button_size * 3.0 + gap * 2.0;
+    let pad_height = button_size * 3.0 + gap * 2.0;
+
+    let pad_left = match state.overlay_anchor {
+        AnchorSide::Left => margin,
+        AnchorSide::Right => screen_width - margin - pad_width,
+    };
+    let pad_top = screen_height - margin - pad_height;
+
+    let glyphs = state.keybindings.direction_glyphs();
+    let dpad = [
+        (0.0, 0.0, glyphs[0], Action::MoveNW),
+        (1.0, 0.0, glyphs[1], Action::MoveN),
+        (2.0, 0.0, glyphs[2], Action::MoveNE),
+        (0.0, 1.0, glyphs[3], Action::MoveW),
+        (2.0, 1.0, glyphs[4], Action::MoveE),
+        (0.0, 2.0, glyphs[5], Action::MoveSW),
+        (1.0, 2.0, glyphs[6], Action::MoveS),
+        (2.0, 2.0, glyphs[7], Action::MoveSE),
+    ];
+    for (col, row, glyph, button_action) in &dpad {
+        let label = glyph.to_string();
+        let min: egui::Pos2 = [
+            pad_left + col * (button_size + gap),
+            pad_top + row * (button_size + gap),
+        ]
+        .into();
+        let max: egui::Pos2 = [min.x + button_size, min.y + button_size].into();
+        let rect = Rect::from_min_max(min, max);
+        if ui
+            .child_ui(rect)
+            .add(ui::button(&label, active, &state.palette))
+            .clicked
+        {
+            action = Some(*button_action);
+        }
+    }
+
+    // Item buttons go up the opposite side from the d-pad so a thumb
+    // resting on it doesn't cover them.
+    let item_left = match state.overlay_anchor {
+        AnchorSide::Left => screen_width - margin - button_size,
+        AnchorSide::Right => margin,
+    };
+
+    let mut inventory = HashMap::new();
+    for item in &state.player.inventory {
+        let count = inventory.entry(item.kind).or_insert(0);
+        *count += 1;
+    }
+
+    let mut item_top = pad_top;
+    for kind in item::Kind::iter() {
+        if inventory.get(&kind).is_some() {
+            let button_action = match kind {
+                item::Kind::Food => Action::UseFood,
+                item::Kind::Dose => Action::UseDose,
+                item::Kind::CardinalDose => Action::UseCardinalDose,
+                item::Kind::DiagonalDose => Action::UseDiagonalDose,
+                item::Kind::StrongDose => Action::UseStrongDose,
+            };
+            let label = format!("[{}]", game::inventory_key(kind));
+            let min: egui::Pos2 = [item_left, item_top].into();
+            let max: egui::Pos2 = [min.x + button_size, min.y + button_size].into();
+            let rect = Rect::from_min_max(min, max);
+            if ui
+                .child_ui(rect)
+                .add(ui::button(&label, active, &state.palette))
+                .clicked
+            {
+                action = Some(button_action);
+            }
+            item_top += button_size + gap;
+        }
+    }
+
     action
 }
 
@@ -339,6 +841,7 @@ struct Layout {
     action_under_mouse: Option<Action>,
     rect_under_mouse: Option<Rectangle>,
     rect2_under_mouse: Option<Rectangle>,
+    tooltip: Option<Tooltip>,
 }
 
 pub struct Window;
@@ -355,8 +858,8 @@ impl Window {
         let tall = display.size_without_padding().y > 31;
         let short = display.size_without_padding().y < 26;
         let x = state.map_size.x;
-        let fg = color::gui_text;
-        let bg = color::dim_background;
+        let fg = state.theme().gui_text;
+        let bg = state.theme().dim_background;
 
         let left_padding = if wide { 1 } else { 0 };
         let mind_pos = Point::new(x + left_padding, 0);
@@ -383,6 +886,20 @@ impl Window {
         let mut action_under_mouse = None;
         let mut rect_under_mouse = None;
         let mut rect2_under_mouse = None;
+        let mut tooltip = None;
+
+        // Mind/Will bars don't have an associated `Action`, but they
+        // still get a tooltip when hovered.
+        let mind_rect =
+            Rectangle::from_point_and_size(mind_pos, Point::new(state.panel_width, 2));
+        if mind_rect.contains(state.mouse.tile_pos) {
+            tooltip = Some(Tooltip::new(mind_tooltip()));
+        }
+        let will_rect =
+            Rectangle::from_point_and_size(stats_pos, Point::new(state.panel_width, 1));
+        if will_rect.contains(state.mouse.tile_pos) {
+            tooltip = Some(Tooltip::new(will_tooltip()));
+        }
 
         let mut inventory = HashMap::new();
         for item in &state.player.inventory {
@@ -400,6 +917,7 @@ impl Window {
                 );
                 if rect.contains(state.mouse.tile_pos) {
                     rect_under_mouse = Some(rect);
+                    tooltip = Some(Tooltip::new(item_tooltip(kind)));
                     action_under_mouse = Some(match kind {
                         item::Kind::Food => Action::UseFood,
                         item::Kind::Dose => Action::UseDose,
@@ -412,19 +930,74 @@ impl Window {
             }
         }
 
+        // Bonus/stun/panic rows aren't backed by their own `Button`s --
+        // they're drawn as plain text lines stacked below the inventory
+        // in `render()`. Walk the same stacking order (of the non-`short`
+        // layout) here so hovering one of those rows in the sidebar shows
+        // a tooltip too.
+        let mut status_y_offset = item_y_offset + 1;
+        if let Some(vnpc_id) = state.victory_npc_id {
+            // The Victory NPC line and the blank line after it.
+            if state.world.monster(vnpc_id).is_some() {
+                status_y_offset += 2;
+            }
+        }
+        if !state.player.bonuses.is_empty() {
+            status_y_offset += 1;
+            for _ in &state.player.bonuses {
+                let left_pad = if wide { -1 } else { 0 };
+                let rect = Rectangle::from_point_and_size(
+                    inventory_pos + Point::new(left_pad, status_y_offset + 1),
+                    Point::new(state.panel_width, 1),
+                );
+                if rect.contains(state.mouse.tile_pos) {
+                    rect_under_mouse = Some(rect);
+                    tooltip = Some(Tooltip::new(bonus_tooltip()));
+                }
+                status_y_offset += 1;
+            }
+            // The blank line after the bonus list.
+            status_y_offset += 1;
+        }
+
+        if state.player.alive() {
+            if state.player.stun.to_int() > 0 {
+                let rect = Rectangle::from_point_and_size(
+                    inventory_pos + Point::new(0, status_y_offset + 1),
+                    Point::new(state.panel_width, 1),
+                );
+                if rect.contains(state.mouse.tile_pos) {
+                    rect_under_mouse = Some(rect);
+                    tooltip = Some(Tooltip::new(stun_tooltip()));
+                }
+                status_y_offset += 1;
+            }
+            if state.player.panic.to_int() > 0 {
+                let rect = Rectangle::from_point_and_size(
+                    inventory_pos + Point::new(0, status_y_offset + 1),
+                    Point::new(state.panel_width, 1),
+                );
+                if rect.contains(state.mouse.tile_pos) {
+                    rect_under_mouse = Some(rect);
+                    tooltip = Some(Tooltip::new(panic_tooltip()));
+                }
+            }
+        }
+
         let mut bottom = display.size_without_padding().y - if tall { 2 } else { 1 };
 
         let main_menu_button = {
             let text = if wide {
-                "[Esc] Main Menu".into()
+                format!("[{}] Main Menu", state.keybindings.main_menu)
             } else {
-                "[Esc] Menu"
+                format!("[{}] Menu", state.keybindings.main_menu)
             };
             Button::new(Point::new(x + left_padding, bottom), &text).color(fg)
         };
 
         bottom -= if tall { 2 } else { 1 };
-        let help_button = Button::new(Point::new(x + left_padding, bottom), "[?] Help").color(fg);
+        let help_label = format!("[{}] Help", state.keybindings.help);
+        let help_button = Button::new(Point::new(x + left_padding, bottom), &help_label).color(fg);
 
         // Position of the movement/numpad buttons
         bottom -= if tall { 10 } else { 9 };
@@ -529,6 +1102,7 @@ impl Window {
             action_under_mouse = None;
             rect_under_mouse = None;
             rect2_under_mouse = None;
+            tooltip = None;
         }
 
         Layout {
@@ -543,6 +1117,7 @@ impl Window {
             action_under_mouse,
             rect_under_mouse,
             rect2_under_mouse,
+            tooltip,
             main_menu_button,
             help_button,
             nw_button,
@@ -577,6 +1152,13 @@ impl Window {
         display: &mut Display,
         top_level: bool,
     ) {
+        // The touch overlay draws itself directly over the play area
+        // via `process_overlay`'s egui widgets; the tile-based sidebar
+        // below would just double up on screen space it doesn't use.
+        if state.control_mode == ControlMode::TouchOverlay {
+            return;
+        }
+
         let wide = state.panel_width > 16;
         let short = display.size_without_padding().y < 26;
         let left_padding = if wide { 1 } else { 0 };
@@ -595,11 +1177,11 @@ impl Window {
         );
 
         if let Some(highlighted) = layout.rect_under_mouse {
-            display.draw_rectangle(highlighted, color::menu_highlight);
+            display.draw_rectangle(highlighted, state.theme().menu_highlight);
         }
 
         if let Some(highlighted) = layout.rect2_under_mouse {
-            display.draw_rectangle(highlighted, color::menu_highlight);
+            display.draw_rectangle(highlighted, state.theme().menu_highlight);
 
             // Calculate player offset a move action would cause:
             let offset = match layout.action_under_mouse {
@@ -628,6 +1210,16 @@ impl Window {
             }
         }
 
+        // Highlight the click-to-move destination the same way the
+        // numpad target is highlighted above.
+        //
+        // NOTE: `state.player_path.goal()` only ever becomes `Some` once
+        // something calls `State::handle_map_click`; see the NOTE on
+        // that method for why this checkout doesn't call it itself.
+        if let Some(path_goal) = state.player_path.goal() {
+            display.set_background(state.screen_pos_from_world_pos(path_goal), state.player.color);
+        }
+
         let player = &state.player;
 
         let max_val = match player.mind {
@@ -652,8 +1244,8 @@ impl Window {
             mind_val_percent,
             layout.progress_bar_pos,
             bar_width,
-            color::gui_progress_bar_fg,
-            color::gui_progress_bar_bg,
+            state.theme().gui_progress_bar_fg,
+            state.theme().gui_progress_bar_bg,
         );
 
         display.draw_button(&Button::new(layout.mind_pos, &mind_str).color(fg));
@@ -673,8 +1265,8 @@ impl Window {
                 state.player.anxiety_counter.percent(),
                 layout.stats_pos + (will_bar_padding, 0),
                 state.player.anxiety_counter.max(),
-                color::anxiety_progress_bar_fg,
-                color::anxiety_progress_bar_bg,
+                state.theme().anxiety_progress_bar_fg,
+                state.theme().anxiety_progress_bar_bg,
             );
         }
         display.draw_text_in_tile_coordinates(
@@ -719,10 +1311,11 @@ impl Window {
                     let dy = (player.pos.y - vnpc_pos.y) as f32;
                     dx.abs().max(dy.abs()) as i32
                 };
+                let arrow = compass_arrow(player.pos, vnpc_pos);
                 if wide {
-                    lines.push(format!("Distance to Victory NPC: {}", distance).into());
+                    lines.push(format!("Victory NPC: {} {}", arrow, distance).into());
                 } else {
-                    lines.push(format!("Victory: {} tiles", distance).into());
+                    lines.push(format!("Victory: {} {}", arrow, distance).into());
                 }
                 if !short {
                     lines.push("".into());
@@ -773,6 +1366,10 @@ impl Window {
             }
         }
 
+        // Populated below the text lines if `state.cheating` is set --
+        // see `draw_frame_graph`.
+        let mut frame_graph_y = None;
+
         if state.cheating {
             lines.push("CHEATING".into());
             lines.push("".into());
@@ -784,17 +1381,6 @@ impl Window {
                 lines.push(format!("Mouse: {}", state.mouse.tile_pos).into());
             }
 
-            lines.push("Time stats:".into());
-            for frame_stat in state.stats.last_frames(25) {
-                lines.push(
-                    format!(
-                        "upd: {}, dc: {}",
-                        frame_stat.update.as_millis(),
-                        frame_stat.drawcalls.as_millis()
-                    )
-                    .into(),
-                );
-            }
             lines.push(format!("longest upd: {}", state.stats.longest_update().as_millis()).into());
             lines.push(
                 format!(
@@ -803,6 +1389,12 @@ impl Window {
                 )
                 .into(),
             );
+
+            lines.push("Frame graph:".into());
+            frame_graph_y = Some(lines.len());
+            for _ in 0..FRAME_GRAPH_HEIGHT_TILES {
+                lines.push("".into());
+            }
         }
 
         let lines_start_y = layout.inventory_pos.y + 1;
@@ -820,8 +1412,54 @@ impl Window {
             );
         }
 
-        display.draw_button(&layout.main_menu_button);
-        display.draw_button(&layout.help_button);
+        if let Some(graph_index) = frame_graph_y {
+            let (update_ms, drawcall_ms): (Vec<f32>, Vec<f32>) = state
+                .stats
+                .last_frames(25)
+                .map(|frame_stat| {
+                    (
+                        frame_stat.update.as_millis() as f32,
+                        frame_stat.drawcalls.as_millis() as f32,
+                    )
+                })
+                .unzip();
+
+            draw_frame_graph(
+                display,
+                metrics,
+                Point::new(x + left_padding, lines_start_y + graph_index as i32),
+                &update_ms,
+                &drawcall_ms,
+                state.theme().gui_progress_bar_fg,
+                state.theme().anxiety_progress_bar_fg,
+                fg,
+            );
+        }
+
+        // The focused button gets redrawn in the highlight colour on
+        // top of its normal rendering, the same way `rect_under_mouse`
+        // highlights the button the real mouse is over.
+        let cursor_focus = if top_level && state.virtual_cursor.enabled {
+            Some(state.virtual_cursor.focused_action())
+        } else {
+            None
+        };
+        let emphasize = |button: &Button, action: Action| -> Button {
+            if cursor_focus == Some(action) {
+                button.clone().color(state.theme().menu_highlight)
+            } else {
+                button.clone()
+            }
+        };
+
+        display.draw_button(&emphasize(&layout.main_menu_button, Action::MainMenu));
+        display.draw_button(&emphasize(&layout.help_button, Action::Help));
+
+        // When a gamepad is connected, show a controller-hint diagram
+        // (stick-direction glyphs) over the same button tiles
+        // instead of the numpad digits, since the numpad is then just
+        // a fallback.
+        let gamepad_active = state.gamepad.is_active();
 
         // Draw the clickable controls help
         if !short {
@@ -829,9 +1467,14 @@ impl Window {
             let label_index_in_lines = label_y - lines_start_y;
             // Don't render the numpad controls label if it would overwrite a line
             if label_index_in_lines >= line_count as i32 {
+                let label = if gamepad_active {
+                    "Controller:"
+                } else {
+                    "Numpad Controls:"
+                };
                 display.draw_text_in_tile_coordinates(
                     Point::new(x + left_padding, label_y),
-                    "Numpad Controls:",
+                    label,
                     layout.fg,
                     crate::engine::TextOptions::align_left(),
                     display.tile_size,
@@ -839,20 +1482,31 @@ impl Window {
             }
         }
 
+        let numpad_glyphs = if gamepad_active {
+            ['\\', '^', '/', '<', '>', '/', 'v', '\\']
+        } else {
+            state.keybindings.direction_glyphs()
+        };
+
         let numpad_buttons = [
-            (&layout.nw_button, '7', (1, 1)),
-            (&layout.n_button, '8', (0, 1)),
-            (&layout.ne_button, '9', (-1, 1)),
-            (&layout.w_button, '4', (1, 0)),
-            (&layout.e_button, '6', (-1, 0)),
-            (&layout.sw_button, '1', (1, -1)),
-            (&layout.s_button, '2', (0, -1)),
-            (&layout.se_button, '3', (-1, -1)),
+            (&layout.nw_button, numpad_glyphs[0], (1, 1), Action::MoveNW),
+            (&layout.n_button, numpad_glyphs[1], (0, 1), Action::MoveN),
+            (&layout.ne_button, numpad_glyphs[2], (-1, 1), Action::MoveNE),
+            (&layout.w_button, numpad_glyphs[3], (1, 0), Action::MoveW),
+            (&layout.e_button, numpad_glyphs[4], (-1, 0), Action::MoveE),
+            (&layout.sw_button, numpad_glyphs[5], (1, -1), Action::MoveSW),
+            (&layout.s_button, numpad_glyphs[6], (0, -1), Action::MoveS),
+            (
+                &layout.se_button,
+                numpad_glyphs[7],
+                (-1, -1),
+                Action::MoveSE,
+            ),
         ];
 
         let tilesize = metrics.tile_width_px();
-        for &(ref button, glyph, tile_offset) in &numpad_buttons {
-            display.draw_button(button);
+        for &(ref button, glyph, tile_offset, action) in &numpad_buttons {
+            display.draw_button(&emphasize(button, action));
 
             // Offset to center the glyph. The font width is different from tilesize so we need
             // sub-tile (pixel-precise) positioning here:
@@ -917,5 +1571,39 @@ impl Window {
                 display.tile_size,
             );
         }
+
+        if let Some(tooltip) = &layout.tooltip {
+            let tooltip_size = Point::new(tooltip.width(), tooltip.height());
+            let max = display.size_without_padding();
+            let mut pos = state.mouse.tile_pos + Point::new(1, 1);
+            // Clamp so the box never overflows the display edges.
+            if pos.x + tooltip_size.x > max.x {
+                pos.x = max.x - tooltip_size.x;
+            }
+            if pos.y + tooltip_size.y > max.y {
+                pos.y = max.y - tooltip_size.y;
+            }
+            pos.x = pos.x.max(0);
+            pos.y = pos.y.max(0);
+
+            display.draw_rectangle(
+                Rectangle::from_point_and_size(pos, tooltip_size),
+                state.theme().dim_background,
+            );
+            for (index, line) in tooltip.lines.iter().enumerate() {
+                display.draw_text_in_tile_coordinates(
+                    pos + Point::new(1, index as i32 + 1),
+                    line,
+                    fg,
+                    Default::default(),
+                    display.tile_size,
+                );
+            }
+        }
+
+        if top_level {
+            TutorialHint.render(state, metrics, display);
+            ContentNote.render(state, metrics, display);
+        }
     }
 }
@@ -0,0 +1,78 @@
+//! Walks the `replays/` fixture directory and replays every log in it
+//! headlessly, asserting each one still reproduces bit-for-bit. Add a
+//! replay here whenever a change to `formula`, `world`, `monster` or
+//! `pathfinding` is worth pinning down as a regression fixture.
+
+use std::fs;
+use std::path::Path;
+
+use dose_response::palette::Palette;
+use dose_response::point::Point;
+use dose_response::state::{Challenge, State};
+
+const WORLD_SIZE: Point = Point {
+    x: 1_073_741_824,
+    y: 1_073_741_824,
+};
+const MAP_SIZE: Point = Point { x: 30, y: 30 };
+const PANEL_WIDTH: i32 = 17;
+
+// NOTE: the actual per-turn simulation step (applying a replayed
+// `Input` to the live `State`) is `game::update`. Per
+// `engine::loop_state::LoopState::update_game`, calling it for real
+// needs a `Settings`, a `SettingsStore`, a `Display` and an
+// `egui::CtxRef` -- none of which this checkout's `settings.rs` /
+// `engine/mod.rs` / `engine/opengl.rs` (not part of this editable
+// snapshot) expose a way to construct outside the full windowed
+// engine. So this can't yet be wired into a plain `#[test]` without
+// guessing at those types' shapes. Until it can, the step function
+// below only pops the next recorded `Input` without applying it --
+// enough to keep `State::verify_replay`'s loop bounded (it drains
+// `state.inputs` the same way a real step would, so it can't spin
+// forever the way a true no-op step did), but not enough to actually
+// advance the simulation. This test is `#[ignore]`d because of that:
+// every fixture is expected to diverge at turn 0 once real input
+// stops lining up with the recorded digests, which is not the bug
+// this test exists to catch. If that annotation is ever dropped by
+// mistake, it must fail loudly via the assertion below instead of via
+// an unrelated hang or panic in the step closure.
+#[ignore = "needs game::update wired in as the replay step function -- see the NOTE above"]
+#[test]
+fn stored_replays_still_reproduce_bit_for_bit() {
+    let fixtures_dir = Path::new("replays");
+    if !fixtures_dir.is_dir() {
+        return;
+    }
+
+    for entry in fs::read_dir(fixtures_dir).expect("failed to read the replays/ directory") {
+        let path = entry
+            .expect("failed to read a replays/ directory entry")
+            .path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let report = State::verify_replay(
+            WORLD_SIZE,
+            MAP_SIZE,
+            PANEL_WIDTH,
+            &path,
+            Challenge::default(),
+            Palette::default(),
+            |state| {
+                state.inputs.pop_front();
+            },
+        )
+        .unwrap_or_else(|err| panic!("failed to replay '{}': {}", path.display(), err));
+
+        assert_eq!(
+            report.diverged_at_turn,
+            None,
+            "replay '{}' diverged at turn {:?} (note: the step function doesn't apply \
+             inputs through game::update, so this only proves fixture discovery and \
+             checkpoint bookkeeping work -- see the #[ignore] reason)",
+            path.display(),
+            report.diverged_at_turn
+        );
+    }
+}